@@ -127,6 +127,8 @@ fn verbose_enabled() -> bool {
 fn resolve_format(raw: &str) -> &'static str {
     if raw.eq_ignore_ascii_case("json") {
         "json"
+    } else if raw.eq_ignore_ascii_case("json-stream") {
+        "json-stream"
     } else if raw.eq_ignore_ascii_case("debug") {
         "debug"
     } else {
@@ -147,6 +149,7 @@ fn collect_input(args: &Args) -> String {
 fn print_engine_output(engine: &meteor::MeteorEngine, input: &str, verbose: bool, format: &str) {
     match format {
         "json" => print_json_engine_output(engine, input, verbose),
+        "json-stream" => print_json_stream_engine_output(engine),
         "debug" => print_debug_engine_output(engine, input),
         _ => print_text_engine_output(engine, input, verbose),
     }
@@ -268,6 +271,42 @@ fn print_json_engine_output(engine: &meteor::MeteorEngine, _input: &str, _verbos
     println!("{}", serde_json::to_string_pretty(&json_output).unwrap());
 }
 
+/// Write a JSON array to stdout, one element at a time
+///
+/// `fill` pushes the elements; the array is opened and closed here. Write
+/// errors are reported on stderr and turn the result into `false`.
+fn write_json_stream(
+    fill: impl FnOnce(&mut meteor::JsonArrayWriter<std::io::StdoutLock<'static>>) -> std::io::Result<()>,
+) -> bool {
+    let result = meteor::JsonArrayWriter::new(std::io::stdout().lock()).and_then(|mut writer| {
+        fill(&mut writer)?;
+        writer.finish().map(|_| ())
+    });
+    match result {
+        Ok(()) => true,
+        Err(err) => {
+            eprintln!("Error writing JSON output: {}", err);
+            false
+        }
+    }
+}
+
+/// Stream every stored entry as one JSON array element, without building
+/// the whole document in memory first
+fn print_json_stream_engine_output(engine: &meteor::MeteorEngine) {
+    write_json_stream(|writer| {
+        for (context, namespace, key, value) in engine.iter_entries() {
+            writer.push_fields(&[
+                ("context", context.as_str()),
+                ("namespace", namespace.as_str()),
+                ("key", key.as_str()),
+                ("value", value.as_str()),
+            ])?;
+        }
+        Ok(())
+    });
+}
+
 fn print_debug_engine_output(engine: &meteor::MeteorEngine, input: &str) {
     println!("=== DEBUG: Meteor Engine Analysis ===");
     println!("Raw input: {:?}", input);
@@ -282,7 +321,7 @@ fn print_debug_engine_output(engine: &meteor::MeteorEngine, input: &str) {
 
 fn print_tokens_output(tokens: &[meteor::Token], format: &str, verbose: bool) {
     match format {
-        "json" => print_json_tokens_output(tokens),
+        "json" | "json-stream" => print_json_tokens_output(tokens),
         "debug" => print_debug_tokens_output(tokens),
         _ => print_text_tokens_output(tokens, verbose),
     }
@@ -325,26 +364,19 @@ fn print_text_tokens_output(tokens: &[meteor::Token], verbose: bool) {
 }
 
 fn print_json_tokens_output(tokens: &[meteor::Token]) {
-    println!("[");
-    for (index, token) in tokens.iter().enumerate() {
-        println!("  {{");
-        match token.namespace() {
-            Some(namespace) => println!("    \"namespace\": \"{}\",", namespace.to_string()),
-            None => println!("    \"namespace\": null,"),
-        }
-        println!("    \"key\": \"{}\",", token.key().to_string());
-        println!(
-            "    \"key_transformed\": \"{}\",",
-            token.key().transformed()
-        );
-        println!("    \"value\": \"{}\"", token.value());
-        if index + 1 < tokens.len() {
-            println!("  }},");
-        } else {
-            println!("  }}");
-        }
-    }
-    println!("]");
+    use serde_json::json;
+
+    write_json_stream(|writer| {
+        for token in tokens {
+            writer.push(&json!({
+                "namespace": token.namespace().map(|namespace| namespace.to_string()),
+                "key": token.key().to_string(),
+                "key_transformed": token.key().transformed(),
+                "value": token.value(),
+            }))?;
+        }
+        Ok(())
+    });
 }
 
 fn print_debug_tokens_output(tokens: &[meteor::Token]) {
@@ -526,19 +558,33 @@ fn list_command(args: Args) -> i32 {
     let namespace = if parts.len() > 1 { parts[1] } else { "" };
 
     let engine = meteor::MeteorEngine::new();
+
+    if format == "json-stream" {
+        // Stream straight from the engine instead of collecting the entries
+        let streamed = write_json_stream(|writer| {
+            for (_, entry_namespace, key, value) in engine.iter_entries_in(context, namespace) {
+                if entry_namespace == namespace {
+                    writer.push_fields(&[("key", key.as_str()), ("value", value.as_str())])?;
+                }
+            }
+            Ok(())
+        });
+        return if streamed { 0 } else { 1 };
+    }
+
     let storage = engine.storage();
     let entries = storage.get_all_keys_in_namespace(context, namespace);
 
     if entries.is_empty() {
         match format {
             "json" => {
-                println!("{{");
-                println!("  \"context\": \"{}\",", context);
+                let mut root = serde_json::Map::new();
+                root.insert("context".to_string(), context.into());
                 if !namespace.is_empty() {
-                    println!("  \"namespace\": \"{}\",", namespace);
+                    root.insert("namespace".to_string(), namespace.into());
                 }
-                println!("  \"entries\": []");
-                println!("}}");
+                root.insert("entries".to_string(), serde_json::Value::Array(Vec::new()));
+                println!("{}", serde_json::to_string_pretty(&root).unwrap());
             }
             _ => {
                 if namespace.is_empty() {
@@ -553,24 +599,17 @@ fn list_command(args: Args) -> i32 {
 
     match format {
         "json" => {
-            println!("{{");
-            println!("  \"context\": \"{}\",", context);
+            let mut root = serde_json::Map::new();
+            root.insert("context".to_string(), context.into());
             if !namespace.is_empty() {
-                println!("  \"namespace\": \"{}\",", namespace);
-            }
-            println!("  \"entries\": [");
-            for (index, (key, value)) in entries.iter().enumerate() {
-                println!("    {{");
-                println!("      \"key\": \"{}\",", key);
-                println!("      \"value\": \"{}\"", value);
-                if index + 1 < entries.len() {
-                    println!("    }},");
-                } else {
-                    println!("    }}");
-                }
+                root.insert("namespace".to_string(), namespace.into());
             }
-            println!("  ]");
-            println!("}}");
+            let items = entries
+                .iter()
+                .map(|(key, value)| serde_json::json!({ "key": key, "value": value }))
+                .collect();
+            root.insert("entries".to_string(), serde_json::Value::Array(items));
+            println!("{}", serde_json::to_string_pretty(&root).unwrap());
         }
         _ => {
             if namespace.is_empty() {
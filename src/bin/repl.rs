@@ -64,6 +64,8 @@ fn run_repl(engine: &mut meteor::MeteorEngine) {
                     "list" => handle_list(engine, rest),
                     "mem" => handle_mem(engine, rest),
                     "load" => handle_load(engine, rest),
+                    "export" => handle_export(engine, rest),
+                    "import" => handle_import(engine, rest),
                     "dump" => render_engine(engine),
                     "help" => print_help(),
                     "show" => render_engine(engine),
@@ -255,6 +257,109 @@ fn handle_list(engine: &meteor::MeteorEngine, input: &str) {
     }
 }
 
+fn handle_export(engine: &meteor::MeteorEngine, input: &str) {
+    match export_command(engine, input) {
+        Ok(output) => println!("{}", output),
+        Err(err) => println!("{}", err),
+    }
+}
+
+fn handle_import(engine: &mut meteor::MeteorEngine, input: &str) {
+    let source = if input.is_empty() {
+        read_pasted_block()
+    } else {
+        load_import_source(input)
+    };
+
+    let source = match source {
+        Ok(source) => source,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
+
+    match import_command(engine, &source) {
+        Ok(output) => print!("{}", output),
+        Err(err) => println!("{}", err),
+    }
+}
+
+/// Render `ExportData` for `export <context> <namespace> [json|text]`
+fn export_command(engine: &meteor::MeteorEngine, input: &str) -> Result<String, String> {
+    let mut parts = input.split_whitespace();
+    let (Some(context), Some(namespace)) = (parts.next(), parts.next()) else {
+        return Err("Usage: export <context> <namespace> [json|text]".to_string());
+    };
+
+    let format = match parts.next().unwrap_or("text") {
+        "text" => meteor::types::ExportFormat::Text,
+        "json" => meteor::types::ExportFormat::Json,
+        other => {
+            return Err(format!(
+                "Unknown export format '{}'. Use json or text.",
+                other
+            ))
+        }
+    };
+
+    let export = engine
+        .export_namespace(context, namespace, format)
+        .ok_or_else(|| format!("No entries for {}:{}", context, namespace))?;
+
+    Ok(export.to_string())
+}
+
+/// Apply exported text or JSON via `import_namespace` and render the `ImportResult`
+fn import_command(engine: &mut meteor::MeteorEngine, source: &str) -> Result<String, String> {
+    let source = source.trim();
+    if source.is_empty() {
+        return Err("Usage: import <file-or-pasted>".to_string());
+    }
+
+    let data = if source.starts_with('{') {
+        meteor::types::ExportData::from_json(source)
+    } else {
+        meteor::types::ExportData::from_text(source)
+    }
+    .map_err(|err| format!("Import error: {}", err))?;
+
+    let target = format!("{}:{}", data.context, data.namespace);
+    let result = engine
+        .import_namespace(data)
+        .map_err(|err| format!("Import error: {}", err))?;
+
+    Ok(format!("Imported into {}\n{}", target, result))
+}
+
+fn load_import_source(input: &str) -> Result<String, String> {
+    let path = std::path::Path::new(input);
+    if path.is_file() {
+        std::fs::read_to_string(path).map_err(|err| format!("Read error: {}", err))
+    } else {
+        Ok(input.to_string())
+    }
+}
+
+fn read_pasted_block() -> Result<String, String> {
+    println!("Paste export data, then a line containing only 'end':");
+    let mut block = String::new();
+    loop {
+        let mut line = String::new();
+        match io::stdin().read_line(&mut line) {
+            Ok(0) => break,
+            Ok(_) => {
+                if line.trim() == "end" {
+                    break;
+                }
+                block.push_str(&line);
+            }
+            Err(err) => return Err(format!("Read error: {}", err)),
+        }
+    }
+    Ok(block)
+}
+
 fn render_engine(engine: &meteor::MeteorEngine) {
     let contexts = engine.contexts();
 
@@ -312,6 +417,8 @@ fn print_help() {
     println!("  list <context> [ns]       - list key/value pairs for context/namespace");
     println!("  mem <cmd> [...]           - scratch-pad helpers (see 'mem help')");
     println!("  load <name>               - edit scratch entry (alias for 'mem edit')");
+    println!("  export <ctx> <ns> [fmt]   - export namespace as text (default) or json");
+    println!("  import [file]             - import export data from file or pasted block");
     println!("  dump | show               - print complete engine state");
     println!("  help                      - this help message");
     println!("  exit | quit               - leave the REPL");
@@ -545,3 +652,49 @@ fn split_name_value(rest: &str) -> Option<(&str, &str)> {
         Some((name, value))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_command_renders_json() {
+        let mut engine = meteor::MeteorEngine::new();
+        engine.set("app:ui:button", "click").unwrap();
+        engine.set("app:ui:theme", "dark").unwrap();
+
+        let output = export_command(&engine, "app ui json").unwrap();
+        assert!(output.contains("\"context\": \"app\""));
+        assert!(output.contains("\"key\": \"button\""));
+        assert!(output.contains("\"value\": \"dark\""));
+
+        let text = export_command(&engine, "app ui").unwrap();
+        assert!(text.starts_with("# Meteor Export"));
+        assert!(text.contains("theme=dark"));
+
+        assert!(export_command(&engine, "app missing").is_err());
+        assert!(export_command(&engine, "app ui yaml").is_err());
+    }
+
+    #[test]
+    fn test_import_command_reports_diff() {
+        let mut source = meteor::MeteorEngine::new();
+        source.set("app:ui:theme", "dark").unwrap();
+        source.set("app:ui:button", "click").unwrap();
+        let exported = export_command(&source, "app ui text").unwrap();
+
+        let mut engine = meteor::MeteorEngine::new();
+        engine.set("app:ui:theme", "light").unwrap();
+
+        let output = import_command(&mut engine, &exported).unwrap();
+        assert!(output.starts_with("Imported into app:ui"));
+        assert!(output.contains("Added: 1"));
+        assert!(output.contains("Updated: 1"));
+        assert!(output.contains("~ theme: light → dark"));
+        assert!(output.contains("+ button: click"));
+        assert!(output.contains("Checksum Valid: true"));
+
+        assert_eq!(engine.get("app:ui:theme"), Some("dark"));
+        assert_eq!(engine.get("app:ui:button"), Some("click"));
+    }
+}
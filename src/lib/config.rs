@@ -73,6 +73,22 @@ pub const MAX_TOKEN_VALUE_LENGTH: usize = {
     }
 };
 
+/// Suggested maximum tokens for a single Meteor
+///
+/// Not enforced by default; opt in with `ParserConfig::with_token_limit()`
+/// or `Meteor::try_new_with_token_limit()`.
+pub const MAX_TOKENS_PER_METEOR: usize = {
+    if cfg!(meteor_enterprise) {
+        10000 // Enterprise: large aggregated meteors
+    } else if cfg!(meteor_embedded) {
+        100 // Embedded: memory limited
+    } else if cfg!(meteor_strict) {
+        50 // Strict: minimal meteors
+    } else {
+        1000 // Default: balanced
+    }
+};
+
 /// Display current configuration profile
 pub fn config_profile() -> &'static str {
     if cfg!(meteor_enterprise) {
@@ -126,6 +142,7 @@ pub fn config_summary() -> String {
              - Max contexts: {}\n\
              - Max token key length: {}\n\
              - Max token value length: {}\n\
+             - Max tokens per meteor: {}\n\
              - Runtime tampering prevention: {}\n\
              - Namespace character validation: {}\n\
              - Reserved word enforcement: {}\n\
@@ -139,6 +156,7 @@ pub fn config_summary() -> String {
         MAX_CONTEXTS,
         MAX_TOKEN_KEY_LENGTH,
         MAX_TOKEN_VALUE_LENGTH,
+        MAX_TOKENS_PER_METEOR,
         prevent_runtime_tampering(),
         validate_namespace_characters(),
         enforce_reserved_words(),
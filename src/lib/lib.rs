@@ -30,12 +30,17 @@ pub mod parser;
 
 // Re-export main public types and functions
 pub use config::{config_profile, config_summary};
-pub use parser::{parse_escaped_value, validate_escapes, MeteorStreamParser, TokenStreamParser};
+pub use parser::{
+    parse_escaped_value, validate_escapes, MeteorStreamParser, ParserConfig, Span, StreamAnalysis,
+    TokenStreamParser,
+};
 pub use types::{
     BracketNotation, Context, ControlCommand, Meteor, MeteorEngine, MeteorError, MeteorShower,
     Namespace, StorageData, Token, TokenKey,
 };
-pub use utils::{is_valid_meteor_format, is_valid_meteor_shower_format, is_valid_token_format};
+pub use utils::{
+    is_valid_meteor_format, is_valid_meteor_shower_format, is_valid_token_format, JsonArrayWriter,
+};
 pub use validation::{is_valid_meteor, is_valid_meteor_shower, is_valid_token};
 
 // ================================
@@ -82,12 +87,20 @@ macro_rules! meteor_parts {
 }
 
 /// Call `MeteorEngine::set` with a canonical path built from segments.
+///
+/// Segments containing `:` are rejected with an `Err` instead of being
+/// joined into a path that would address a different key:
+///
+/// ```
+/// let mut engine = meteor::MeteorEngine::new();
+/// assert!(meteor::meteor_set!(engine, "app", "ui:main", "theme" => "dark").is_err());
+/// assert!(engine.is_empty());
+/// ```
 #[macro_export]
 macro_rules! meteor_set {
-    ($engine:expr, $context:literal : $namespace:literal : $key:literal => $value:expr) => {{
-        let __path = $crate::meteor!($context, $namespace, $key);
-        $engine.set(&__path, $value)
-    }};
+    ($engine:expr, $context:literal : $namespace:literal : $key:literal => $value:expr) => {
+        $crate::meteor_set!($engine, $context, $namespace, $key => $value)
+    };
     ($engine:expr, $context:ident : $namespace:ident : $key:ident => $value:expr) => {{
         let __path = $crate::meteor!(
             stringify!($context),
@@ -97,8 +110,22 @@ macro_rules! meteor_set {
         $engine.set(&__path, $value)
     }};
     ($engine:expr, $context:expr, $namespace:expr, $key:expr => $value:expr) => {{
-        let __path = $crate::meteor!($context, $namespace, $key);
-        $engine.set(&__path, $value)
+        let __segments = (
+            ::std::string::ToString::to_string(&$context),
+            ::std::string::ToString::to_string(&$namespace),
+            ::std::string::ToString::to_string(&$key),
+        );
+        match $crate::utils::validators::validate_path_segments(
+            &__segments.0,
+            &__segments.1,
+            &__segments.2,
+        ) {
+            Ok(()) => {
+                let __path = $crate::meteor!(__segments.0, __segments.1, __segments.2);
+                $engine.set(&__path, $value)
+            }
+            Err(err) => Err(err.to_string()),
+        }
     }};
     ($engine:expr, $path:expr => $value:expr) => {{
         let __path = $crate::meteor!($path);
@@ -107,8 +134,28 @@ macro_rules! meteor_set {
 }
 
 /// Retrieve a value using a canonical meteor path.
+///
+/// Append `default => fallback` to get a `&str` instead of an `Option`:
+///
+/// ```
+/// let engine = meteor::MeteorEngine::new();
+/// let theme = meteor::meteor_get!(engine, "app:ui:theme", default => "light");
+/// assert_eq!(theme, "light");
+/// ```
 #[macro_export]
 macro_rules! meteor_get {
+    ($engine:expr, $context:literal : $namespace:literal : $key:literal, default => $default:expr) => {
+        $crate::meteor_get!($engine, $context: $namespace: $key).unwrap_or($default)
+    };
+    ($engine:expr, $context:ident : $namespace:ident : $key:ident, default => $default:expr) => {
+        $crate::meteor_get!($engine, $context: $namespace: $key).unwrap_or($default)
+    };
+    ($engine:expr, $context:expr, $namespace:expr, $key:expr, default => $default:expr) => {
+        $crate::meteor_get!($engine, $context, $namespace, $key).unwrap_or($default)
+    };
+    ($engine:expr, $path:expr, default => $default:expr) => {
+        $crate::meteor_get!($engine, $path).unwrap_or($default)
+    };
     ($engine:expr, $context:literal : $namespace:literal : $key:literal) => {{
         let __path = $crate::meteor!($context, $namespace, $key);
         $engine.get(&__path)
@@ -156,27 +203,76 @@ macro_rules! meteor_delete {
     }};
 }
 
-/// Store a default (`*.index`) value for a context/namespace pair.
+/// Store a default (`index`, see `MeteorEngine::default_key_for`) value for a context/namespace pair.
 #[macro_export]
 macro_rules! meteor_default {
     ($engine:expr, $context:literal : $namespace:literal => $value:expr) => {{
-        let __path = ::std::format!("{}:{}:index", $context, $namespace);
+        let __path = ::std::format!(
+            "{}:{}:{}",
+            $context,
+            $namespace,
+            $crate::types::MeteorEngine::default_key_for("")
+        );
         $engine.set(&__path, $value)
     }};
     ($engine:expr, $context:ident : $namespace:ident => $value:expr) => {{
-        let __path = ::std::format!("{}:{}:index", stringify!($context), stringify!($namespace));
+        let __path = ::std::format!(
+            "{}:{}:{}",
+            stringify!($context),
+            stringify!($namespace),
+            $crate::types::MeteorEngine::default_key_for("")
+        );
         $engine.set(&__path, $value)
     }};
     ($engine:expr, $context:expr, $namespace:expr => $value:expr) => {{
-        let __path = ::std::format!("{}:{}:index", $context, $namespace);
+        let __path = ::std::format!(
+            "{}:{}:{}",
+            $context,
+            $namespace,
+            $crate::types::MeteorEngine::default_key_for("")
+        );
         $engine.set(&__path, $value)
     }};
     ($engine:expr, $context:expr => $value:expr) => {{
-        let __path = ::std::format!("{}:{}:index", $context, "main");
+        let __path = ::std::format!(
+            "{}:{}:{}",
+            $context,
+            "main",
+            $crate::types::MeteorEngine::default_key_for("")
+        );
         $engine.set(&__path, $value)
     }};
 }
 
+/// Build a `MeteorShower` from `context:namespace:key => value` entries, like `vec!`.
+///
+/// Each entry becomes its own single-token meteor, added in order.
+///
+/// ```
+/// let shower = meteor::meteor_shower! {
+///     "app":"ui":"button" => "click";
+///     "user":"settings":"theme" => "dark";
+/// };
+/// assert_eq!(shower.len(), 2);
+/// ```
+#[macro_export]
+macro_rules! meteor_shower {
+    () => {
+        $crate::MeteorShower::new()
+    };
+    ($($context:literal : $namespace:literal : $key:literal => $value:expr);+ $(;)?) => {{
+        let mut __shower = $crate::MeteorShower::new();
+        $(
+            __shower.add($crate::Meteor::new(
+                $crate::Context::from($context),
+                $crate::Namespace::from_string($namespace),
+                $crate::Token::new($key, $value),
+            ));
+        )+
+        __shower
+    }};
+}
+
 // Module trait for RSB-compliant module organization
 pub trait Module {
     /// Return the module's name for identification
@@ -230,4 +326,73 @@ mod tests {
         assert_eq!(module.name(), "meteor");
         assert!(!module.version().is_empty());
     }
+
+    #[test]
+    fn test_meteor_get_macro_default() {
+        let mut engine = MeteorEngine::new();
+        engine.set("app:ui:theme", "dark").unwrap();
+
+        assert_eq!(
+            meteor_get!(engine, "app:ui:theme", default => "light"),
+            "dark"
+        );
+        assert_eq!(meteor_get!(engine, "app:ui:lang", default => "en"), "en");
+        assert_eq!(
+            meteor_get!(engine, "app":"ui":"theme", default => "light"),
+            "dark"
+        );
+        assert_eq!(meteor_get!(engine, app:ui:lang, default => "en"), "en");
+        assert_eq!(
+            meteor_get!(engine, "app", "ui", "theme", default => "light"),
+            "dark"
+        );
+        assert_eq!(
+            meteor_get!(engine, "app", "ui", "lang", default => "en"),
+            "en"
+        );
+
+        // Forms without a default still return Option
+        assert_eq!(meteor_get!(engine, "app:ui:lang"), None);
+    }
+
+    #[test]
+    fn test_meteor_shower_macro() {
+        let shower = meteor_shower! {
+            "app":"ui":"button" => "click";
+            "user":"settings":"theme" => "dark"
+        };
+
+        assert_eq!(shower.len(), 2);
+        let button = shower.find("app", "ui", "button").unwrap();
+        assert_eq!(button.token().value(), "click");
+        let theme = shower.find("user", "settings", "theme").unwrap();
+        assert_eq!(theme.token().value(), "dark");
+        assert!(shower.find("app", "ui", "theme").is_none());
+
+        let empty = meteor_shower!();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_meteor_set_macro_rejects_colon_segments() {
+        let mut engine = MeteorEngine::new();
+
+        let err = meteor_set!(engine, "app", "ui:main", "theme" => "dark").unwrap_err();
+        assert_eq!(
+            err,
+            "Invalid character ':' at position 2 in namespace segment 'ui:main'"
+        );
+        let err = meteor_set!(engine, "app":"ui":"a:b" => "dark").unwrap_err();
+        assert!(err.contains("key segment 'a:b'"));
+        let context = String::from("app:x");
+        assert!(meteor_set!(engine, context, "ui", "theme" => "dark").is_err());
+        assert!(engine.is_empty());
+
+        meteor_set!(engine, "app", "ui", "theme" => "dark").unwrap();
+        meteor_set!(engine, "app":"ui":"lang" => "en").unwrap();
+        meteor_set!(engine, app:ui:mode => "compact").unwrap();
+        assert_eq!(engine.get("app:ui:theme"), Some("dark"));
+        assert_eq!(engine.get("app:ui:lang"), Some("en"));
+        assert_eq!(engine.get("app:ui:mode"), Some("compact"));
+    }
 }
@@ -0,0 +1,88 @@
+//! Parser Configuration - Opt-in preprocessing for stream parsers
+//!
+//! `ParserConfig` toggles optional stream features that are off by default so
+//! the legacy `process()` entry points keep their exact behaviour.
+
+use crate::parser::split::{join_continued_lines, strip_comments};
+use std::borrow::Cow;
+
+/// Optional features applied to a stream before token parsing
+#[derive(Debug, Clone, Default)]
+pub struct ParserConfig {
+    /// Strip `#` inline and whole-line comments (quotes are respected)
+    pub allow_comments: bool,
+    /// Join lines ending in an unquoted `\` with the following line
+    pub line_continuations: bool,
+    /// Value given to bare flag tokens with no `=` (e.g. `Some("true")`);
+    /// `None` keeps them an error
+    pub bare_key_value: Option<&'static str>,
+    /// Reject meteors holding more tokens than this with
+    /// `MeteorError::TooManyTokens`; `None` applies no limit
+    pub max_tokens_per_meteor: Option<usize>,
+}
+
+impl ParserConfig {
+    /// Configuration with all optional features disabled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Configuration for annotated streams with `#` comments
+    pub fn with_comments() -> Self {
+        Self {
+            allow_comments: true,
+            ..Self::default()
+        }
+    }
+
+    /// Configuration for streams whose values span lines via `\` continuations
+    pub fn with_line_continuations() -> Self {
+        Self {
+            line_continuations: true,
+            ..Self::default()
+        }
+    }
+
+    /// Configuration treating bare flag tokens like `verbose` as `verbose=<value>`
+    pub fn with_bare_key_value(value: &'static str) -> Self {
+        Self {
+            bare_key_value: Some(value),
+            ..Self::default()
+        }
+    }
+
+    /// Configuration capping the tokens aggregated into one meteor
+    ///
+    /// `config::MAX_TOKENS_PER_METEOR` is the build profile's suggested bound.
+    pub fn with_token_limit(limit: usize) -> Self {
+        Self {
+            max_tokens_per_meteor: Some(limit),
+            ..Self::default()
+        }
+    }
+
+    /// Expand a bare flag token to `key=<value>` when `bare_key_value` is set
+    ///
+    /// Tokens that already contain `=` are returned unchanged.
+    pub fn expand_bare_flag<'a>(&self, token: &'a str) -> Cow<'a, str> {
+        match self.bare_key_value {
+            Some(value) if !token.contains('=') => Cow::Owned(format!("{}={}", token, value)),
+            _ => Cow::Borrowed(token),
+        }
+    }
+
+    /// Apply enabled preprocessing steps to raw stream input
+    ///
+    /// Comments are stripped before continuations are joined, so a trailing
+    /// `\` inside a comment never swallows the next line.
+    pub fn preprocess<'a>(&self, input: &'a str) -> Cow<'a, str> {
+        let mut output = Cow::Borrowed(input);
+        if self.allow_comments {
+            output = Cow::Owned(strip_comments(&output));
+        }
+        if self.line_continuations {
+            output = Cow::Owned(join_continued_lines(&output));
+        }
+        output
+    }
+}
@@ -125,6 +125,54 @@ pub fn strip_quotes(input: &str) -> Result<String, String> {
     }
 }
 
+/// Characters that `escape_delimiters` protects with a backslash
+pub const DELIMITER_ESCAPES: &[char] = &['\\', ';', ':', '=', '"'];
+
+/// Backslash-escape meteor delimiters (`;`, `:`, `=`), quotes and backslashes
+///
+/// The result can be embedded in a meteor string and recovered with
+/// `unescape_delimiters`.
+///
+/// # Examples
+/// ```
+/// use meteor::parser::escape::{escape_delimiters, unescape_delimiters};
+///
+/// assert_eq!(escape_delimiters("a;b"), "a\\;b");
+/// assert_eq!(unescape_delimiters(&escape_delimiters("k=v:1")), "k=v:1");
+/// ```
+pub fn escape_delimiters(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    for ch in input.chars() {
+        if DELIMITER_ESCAPES.contains(&ch) {
+            output.push('\\');
+        }
+        output.push(ch);
+    }
+    output
+}
+
+/// Reverse `escape_delimiters`
+///
+/// Only escapes of the characters in `DELIMITER_ESCAPES` are removed; other
+/// backslash sequences (e.g. `C:\\dir` written as `\\d`) are kept verbatim.
+pub fn unescape_delimiters(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch == '\\' {
+            if let Some(&next) = chars.peek() {
+                if DELIMITER_ESCAPES.contains(&next) {
+                    output.push(next);
+                    chars.next();
+                    continue;
+                }
+            }
+        }
+        output.push(ch);
+    }
+    output
+}
+
 /// Check if a string contains unescaped quotes
 pub fn has_unescaped_quotes(input: &str) -> bool {
     let mut chars = input.chars().peekable();
@@ -207,4 +255,20 @@ mod tests {
         assert!(!has_unescaped_quotes(r#"escaped \" quote"#));
         assert!(!has_unescaped_quotes("no quotes"));
     }
+
+    #[test]
+    fn test_delimiter_escapes_round_trip() {
+        for raw in [
+            "plain",
+            "a;b",
+            "k=v",
+            "http://host:80",
+            "quote\"d",
+            "back\\slash",
+        ] {
+            assert_eq!(unescape_delimiters(&escape_delimiters(raw)), raw);
+        }
+        assert_eq!(escape_delimiters("a:b;c"), "a\\:b\\;c");
+        assert_eq!(unescape_delimiters("C:\\dir"), "C:\\dir");
+    }
 }
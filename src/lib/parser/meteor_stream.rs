@@ -7,7 +7,9 @@
 //! - Delegation to MeteorEngine for state changes
 //! - ENG-41: Meteor aggregation with hardened constructors
 
-use crate::parser::split::{smart_split, smart_split_multi_char, SplitConfig};
+use crate::parser::config::ParserConfig;
+use crate::parser::span::Span;
+use crate::parser::split::{smart_split_borrowed, smart_split_multi_char, SplitConfig};
 use crate::types::{Context, Meteor, MeteorEngine, MeteorError, Namespace, Token};
 use crate::utils::validators::is_valid_meteor_format;
 use std::collections::HashMap;
@@ -16,6 +18,41 @@ use std::str::FromStr;
 /// Meteor delimiter for separating meteors in a stream
 pub const METEOR_DELIMITER: &str = ":;:";
 
+/// Tokens grouped by (context, namespace), plus first-appearance group order
+type GroupedTokens = (
+    Vec<(Context, Namespace)>,
+    HashMap<(Context, Namespace), Vec<Token>>,
+);
+
+/// Summary of a meteor stream produced by `MeteorStreamParser::analyze`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StreamAnalysis {
+    /// Non-empty `:;:`-separated segments
+    pub segment_count: usize,
+    /// Meteors the stream would aggregate into, one per (context, namespace)
+    pub meteor_count: usize,
+    /// Data tokens (`context:namespace:key=value`)
+    pub token_count: usize,
+    /// `ctl:` commands, which would be executed rather than stored
+    pub control_commands: usize,
+    /// `context:namespace` pairs in first-appearance order
+    pub namespaces_touched: Vec<String>,
+    /// Tokens that would fail to parse
+    pub invalid_tokens: usize,
+}
+
+/// One piece of a stream as seen by `MeteorStreamParser::walk`
+enum StreamItem<'a> {
+    /// A non-empty `:;:`-separated segment begins
+    Segment,
+    /// A `ctl:` command, at segment or token level
+    Control,
+    /// A cursor switch (`ns=`, `ctx=`), at segment or token level
+    Cursor,
+    /// Any other non-empty token, trimmed
+    Token(&'a str),
+}
+
 /// Meteor stream parser with validation and delegation
 pub struct MeteorStreamParser;
 
@@ -35,7 +72,128 @@ impl MeteorStreamParser {
         input: &str,
     ) -> Result<(), MeteorError> {
         let (order, grouped_tokens) = Self::parse_explicit_meteors(engine, input)?;
-        Self::store_grouped_tokens(engine, order, grouped_tokens)
+        Self::store_grouped_tokens(engine, order, grouped_tokens, None)
+    }
+
+    /// Parse and process a meteor stream, returning where each token came from
+    ///
+    /// Behaves like `process_with_aggregation()` and additionally returns each
+    /// stored full path (`context:namespace:key`) with the `Span` of its token
+    /// in `input`, in stream order. A key set twice appears twice.
+    ///
+    /// # Examples
+    /// ```
+    /// use meteor::{MeteorEngine, MeteorStreamParser};
+    ///
+    /// let mut engine = MeteorEngine::new();
+    /// let input = "app:ui:button=click :;: app:ui:theme=dark";
+    /// let spans = MeteorStreamParser::process_with_spans(&mut engine, input).unwrap();
+    /// assert_eq!(spans[1].0, "app:ui:theme");
+    /// assert_eq!(spans[1].1.slice(input), "app:ui:theme=dark");
+    /// ```
+    pub fn process_with_spans(
+        engine: &mut MeteorEngine,
+        input: &str,
+    ) -> Result<Vec<(String, Span)>, MeteorError> {
+        let mut spans = Vec::new();
+        let (order, grouped_tokens) = Self::parse_explicit_meteors_with_spans(
+            engine,
+            input,
+            &ParserConfig::default(),
+            Some(&mut spans),
+        )?;
+        Self::store_grouped_tokens(engine, order, grouped_tokens, None)?;
+        Ok(spans)
+    }
+
+    /// Parse and process a meteor stream, skipping segments that fail
+    ///
+    /// Each `:;:`-separated segment is parsed and stored on its own; a segment
+    /// with an error is skipped and its `Span` in `input` is returned with the
+    /// error, while the remaining segments still reach the engine. Every token
+    /// of a segment is checked against the engine before the segment runs any
+    /// control command or write, so a skipped segment changes nothing. Error
+    /// positions are relative to `input`. Returns the number of tokens stored
+    /// and the skipped segments in stream order.
+    ///
+    /// # Examples
+    /// ```
+    /// use meteor::{MeteorEngine, MeteorStreamParser};
+    ///
+    /// let mut engine = MeteorEngine::new();
+    /// let input = "app:ui:button=click :;: broken :;: app:ui:theme=dark";
+    /// let (applied, skipped) = MeteorStreamParser::process_lenient(&mut engine, input);
+    /// assert_eq!(applied, 2);
+    /// assert_eq!(skipped[0].0.slice(input), "broken");
+    /// ```
+    pub fn process_lenient(
+        engine: &mut MeteorEngine,
+        input: &str,
+    ) -> (usize, Vec<(Span, MeteorError)>) {
+        let mut applied = 0;
+        let mut skipped = Vec::new();
+
+        for segment in input.split(METEOR_DELIMITER) {
+            let trimmed = segment.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+
+            let result = Self::check_segment(engine, trimmed)
+                .and_then(|_| Self::parse_explicit_meteors(engine, trimmed))
+                .and_then(|(order, grouped_tokens)| {
+                    let count = grouped_tokens.values().map(Vec::len).sum::<usize>();
+                    Self::store_grouped_tokens(engine, order, grouped_tokens, None).map(|_| count)
+                });
+
+            match result {
+                Ok(count) => applied += count,
+                Err(error) => {
+                    let span = Span::of_slice(input, trimmed);
+                    skipped.push((span, error.offset_by(span.start)));
+                }
+            }
+        }
+
+        (applied, skipped)
+    }
+
+    /// Check every token of a segment as `set()` would, without side effects
+    fn check_segment(engine: &MeteorEngine, segment: &str) -> Result<(), MeteorError> {
+        Self::walk(segment, |item| {
+            let StreamItem::Token(token) = item else {
+                return Ok(());
+            };
+            let (context, namespace, token) = Self::explicit_token(segment, token, None)?;
+            let path = format!("{}:{}:{}", context, namespace, token.key_notation());
+            engine
+                .validate_set(&path, token.value())
+                .map_err(MeteorError::other)
+        })
+    }
+
+    /// Parse and process a stream with optional preprocessing from `ParserConfig`
+    ///
+    /// Applies the configured preprocessing (e.g. comment stripping) and then
+    /// behaves like `process_with_aggregation()`. With `bare_key_value` set,
+    /// a bare `context:namespace:flag` token is stored with that value, and
+    /// with `max_tokens_per_meteor` set, larger groups fail with `TooManyTokens`.
+    ///
+    /// # Examples
+    /// ```ignore
+    /// let mut engine = MeteorEngine::new();
+    /// let config = ParserConfig::with_comments();
+    /// MeteorStreamParser::process_with_config(&mut engine, "app:ui:button=click # primary action", &config)?;
+    /// ```
+    pub fn process_with_config(
+        engine: &mut MeteorEngine,
+        input: &str,
+        config: &ParserConfig,
+    ) -> Result<(), MeteorError> {
+        let input = config.preprocess(input);
+        let (order, grouped_tokens) =
+            Self::parse_explicit_meteors_with_spans(engine, &input, config, None)?;
+        Self::store_grouped_tokens(engine, order, grouped_tokens, config.max_tokens_per_meteor)
     }
 
     /// Parse and process a meteor stream (Legacy method)
@@ -55,13 +213,15 @@ impl MeteorStreamParser {
     pub fn process(engine: &mut MeteorEngine, input: &str) -> Result<(), String> {
         let (order, grouped_tokens) =
             Self::parse_explicit_meteors(engine, input).map_err(|e| e.to_string())?;
-        Self::store_grouped_tokens(engine, order, grouped_tokens).map_err(|e| e.to_string())
+        Self::store_grouped_tokens(engine, order, grouped_tokens, None).map_err(|e| e.to_string())
     }
 
+    /// Store each group as one meteor, rejecting groups past `token_limit`
     fn store_grouped_tokens(
         engine: &mut MeteorEngine,
         order: Vec<(Context, Namespace)>,
         mut grouped_tokens: HashMap<(Context, Namespace), Vec<Token>>,
+        token_limit: Option<usize>,
     ) -> Result<(), MeteorError> {
         for (context, namespace) in order {
             if let Some(tokens) = grouped_tokens.remove(&(context.clone(), namespace.clone())) {
@@ -69,8 +229,17 @@ impl MeteorStreamParser {
                     continue;
                 }
 
-                let meteor =
-                    Meteor::try_new_with_tokens(context.clone(), namespace.clone(), tokens)?;
+                let meteor = match token_limit {
+                    Some(limit) => Meteor::try_new_with_token_limit(
+                        context.clone(),
+                        namespace.clone(),
+                        tokens,
+                        limit,
+                    )?,
+                    None => {
+                        Meteor::try_new_with_tokens(context.clone(), namespace.clone(), tokens)?
+                    }
+                };
                 Self::store_meteor_tokens(engine, &meteor)?;
             }
         }
@@ -121,6 +290,145 @@ impl MeteorStreamParser {
         Ok(())
     }
 
+    /// Inspect a meteor stream without touching any engine
+    ///
+    /// Walks the stream the same way `process_with_aggregation()` does and
+    /// counts what it would do: segments, the meteors tokens would aggregate
+    /// into, data tokens, control commands and the namespaces written.
+    /// Cursor switches (`ns=`, `ctx=`) are skipped since they never change
+    /// explicit addresses. Malformed tokens are tallied in `invalid_tokens`
+    /// instead of aborting the analysis.
+    ///
+    /// # Examples
+    /// ```
+    /// use meteor::MeteorStreamParser;
+    ///
+    /// let analysis = MeteorStreamParser::analyze(
+    ///     "app:ui:button=click;app:ui:theme=dark :;: ctl:reset=cursor :;: user:main:lang=en",
+    /// );
+    /// assert_eq!(analysis.segment_count, 3);
+    /// assert_eq!(analysis.meteor_count, 2);
+    /// assert_eq!(analysis.token_count, 3);
+    /// assert_eq!(analysis.control_commands, 1);
+    /// ```
+    pub fn analyze(input: &str) -> StreamAnalysis {
+        let mut analysis = StreamAnalysis::default();
+
+        let walked: Result<(), MeteorError> = Self::walk(input, |item| {
+            match item {
+                StreamItem::Segment => analysis.segment_count += 1,
+                StreamItem::Control => analysis.control_commands += 1,
+                StreamItem::Cursor => {}
+                StreamItem::Token(token) => {
+                    let address = token
+                        .split_once('=')
+                        .map(|(path, _)| path.split(':').collect::<Vec<_>>())
+                        .filter(|parts| parts.len() == 3)
+                        .and_then(|parts| {
+                            let context = Context::from_str(parts[0]).ok()?;
+                            Some(format!("{}:{}", context, Namespace::from_string(parts[1])))
+                        });
+
+                    match address {
+                        Some(address) => {
+                            analysis.token_count += 1;
+                            if !analysis.namespaces_touched.contains(&address) {
+                                analysis.namespaces_touched.push(address);
+                            }
+                        }
+                        None => analysis.invalid_tokens += 1,
+                    }
+                }
+            }
+            Ok(())
+        });
+        debug_assert!(walked.is_ok(), "analysis visitor never fails");
+
+        analysis.meteor_count = analysis.namespaces_touched.len();
+        analysis
+    }
+
+    /// Count the tokens a stream would store, without parsing them
+    ///
+    /// A cheap preflight for large inputs: segments and tokens are split the
+    /// same way `process()` splits them, control commands and cursor
+    /// switches are skipped, and every other token is counted. A token
+    /// without `=` is an error, as it would be when processing. Keys written
+    /// more than once are counted each time.
+    ///
+    /// # Examples
+    /// ```
+    /// use meteor::MeteorStreamParser;
+    ///
+    /// let count = MeteorStreamParser::estimate("app:ui:a=1;app:ui:b=2 :;: ctl:reset=cursor").unwrap();
+    /// assert_eq!(count, 2);
+    /// assert!(MeteorStreamParser::estimate("app:ui:flag").is_err());
+    /// ```
+    pub fn estimate(input: &str) -> Result<usize, MeteorError> {
+        let mut count = 0;
+
+        Self::walk(input, |item| {
+            if let StreamItem::Token(token) = item {
+                if !token.contains('=') {
+                    return Err(MeteorError::parse(
+                        Span::of_slice(input, token).end,
+                        format!(
+                            "Invalid meteor format: '{}' - missing value assignment",
+                            token
+                        ),
+                    ));
+                }
+                count += 1;
+            }
+            Ok(())
+        })?;
+
+        Ok(count)
+    }
+
+    /// Split a stream the way `process()` does, handing each piece to `visit`
+    ///
+    /// Shared by `analyze()` and `estimate()` so both agree on what counts as
+    /// a segment, a directive and a token. Stops at the first visitor error.
+    fn walk(
+        input: &str,
+        mut visit: impl FnMut(StreamItem<'_>) -> Result<(), MeteorError>,
+    ) -> Result<(), MeteorError> {
+        for meteor_str in input.split(METEOR_DELIMITER) {
+            let trimmed = meteor_str.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+            visit(StreamItem::Segment)?;
+
+            if let Some(directive) = Self::directive(trimmed) {
+                visit(directive)?;
+                continue;
+            }
+
+            for token_str in smart_split_borrowed(trimmed, SplitConfig::meteor_streams(';')) {
+                let token_trimmed = token_str.trim();
+                if token_trimmed.is_empty() {
+                    continue;
+                }
+                visit(Self::directive(token_trimmed).unwrap_or(StreamItem::Token(token_trimmed)))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Classify control commands and cursor switches, which never store a token
+    fn directive(token: &str) -> Option<StreamItem<'static>> {
+        if token.starts_with("ctl:") {
+            Some(StreamItem::Control)
+        } else if token.starts_with("ns=") || token.starts_with("ctx=") {
+            Some(StreamItem::Cursor)
+        } else {
+            None
+        }
+    }
+
     /// Split a stream by delimiter, respecting quotes
     ///
     /// Uses centralized smart-split logic (ENG-42) for meteor delimiter parsing.
@@ -138,13 +446,17 @@ impl MeteorStreamParser {
     fn parse_explicit_meteors(
         engine: &mut MeteorEngine,
         input: &str,
-    ) -> Result<
-        (
-            Vec<(Context, Namespace)>,
-            HashMap<(Context, Namespace), Vec<Token>>,
-        ),
-        MeteorError,
-    > {
+    ) -> Result<GroupedTokens, MeteorError> {
+        Self::parse_explicit_meteors_with_spans(engine, input, &ParserConfig::default(), None)
+    }
+
+    /// Parse explicit meteors, optionally recording each token's path and span
+    fn parse_explicit_meteors_with_spans(
+        engine: &mut MeteorEngine,
+        input: &str,
+        config: &ParserConfig,
+        mut spans: Option<&mut Vec<(String, Span)>>,
+    ) -> Result<GroupedTokens, MeteorError> {
         let mut grouped_tokens: HashMap<(Context, Namespace), Vec<Token>> = HashMap::new();
         let mut order: Vec<(Context, Namespace)> = Vec::new();
         let meteors = input.split(METEOR_DELIMITER);
@@ -175,7 +487,7 @@ impl MeteorStreamParser {
                 continue;
             }
 
-            let token_parts = smart_split(trimmed, SplitConfig::meteor_streams(';'));
+            let token_parts = smart_split_borrowed(trimmed, SplitConfig::meteor_streams(';'));
 
             for token_str in token_parts {
                 let token_trimmed = token_str.trim();
@@ -204,27 +516,14 @@ impl MeteorStreamParser {
                     continue;
                 }
 
-                let (key_value, value) = token_trimmed.split_once('=').ok_or_else(|| {
-                    MeteorError::other(format!(
-                        "Invalid meteor format: '{}' - missing value assignment",
-                        token_trimmed
-                    ))
-                })?;
+                let (context, namespace, token) =
+                    Self::explicit_token(input, token_trimmed, config.bare_key_value)?;
 
-                let key_parts: Vec<&str> = key_value.split(':').collect();
-                if key_parts.len() != 3 {
-                    return Err(MeteorError::other(format!(
-                        "Invalid meteor format: '{}' - expected context:namespace:key=value",
-                        token_trimmed
-                    )));
+                if let Some(spans) = spans.as_mut() {
+                    let path = format!("{}:{}:{}", context, namespace, token.key_notation());
+                    spans.push((path, Span::of_slice(input, token_trimmed)));
                 }
 
-                let context = Context::from_str(key_parts[0]).map_err(|e| {
-                    MeteorError::other(format!("Invalid context '{}': {}", key_parts[0], e))
-                })?;
-                let namespace = Namespace::from_string(key_parts[1]);
-                let token = Token::new(key_parts[2], value);
-
                 let map_key = (context.clone(), namespace.clone());
                 if !grouped_tokens.contains_key(&map_key) {
                     order.push(map_key.clone());
@@ -240,6 +539,48 @@ impl MeteorStreamParser {
     }
 
     /// Store tokens from a validated meteor into the engine
+    /// Split one `context:namespace:key=value` token of `input`
+    ///
+    /// A token without `=` takes `bare_value` when one is given. Error
+    /// positions are relative to `input`.
+    fn explicit_token(
+        input: &str,
+        token: &str,
+        bare_value: Option<&str>,
+    ) -> Result<(Context, Namespace, Token), MeteorError> {
+        let position = Span::of_slice(input, token).start;
+        let (key_value, value) = match (token.split_once('='), bare_value) {
+            (Some(pair), _) => pair,
+            (None, Some(flag_value)) => (token, flag_value),
+            (None, None) => {
+                return Err(MeteorError::parse(
+                    position + token.len(),
+                    format!(
+                        "Invalid meteor format: '{}' - missing value assignment",
+                        token
+                    ),
+                ))
+            }
+        };
+
+        let key_parts: Vec<&str> = key_value.split(':').collect();
+        if key_parts.len() != 3 {
+            return Err(MeteorError::parse(
+                position,
+                format!(
+                    "Invalid meteor format: '{}' - expected context:namespace:key=value",
+                    token
+                ),
+            ));
+        }
+
+        let context = Context::from_str(key_parts[0]).map_err(|e| {
+            MeteorError::other(format!("Invalid context '{}': {}", key_parts[0], e))
+        })?;
+        let namespace = Namespace::from_string(key_parts[1]);
+        Ok((context, namespace, Token::new(key_parts[2], value)))
+    }
+
     fn store_meteor_tokens(engine: &mut MeteorEngine, meteor: &Meteor) -> Result<(), MeteorError> {
         for token in meteor.tokens() {
             let path = format!(
@@ -330,4 +671,243 @@ mod tests {
         assert_eq!(parts[0], "app:ui:button=click");
         assert_eq!(parts[1], "user:main:profile=\"admin :;: test\"");
     }
+
+    #[test]
+    fn test_process_with_config_trailing_comment() {
+        let mut engine = MeteorEngine::new();
+        let config = ParserConfig::with_comments();
+
+        MeteorStreamParser::process_with_config(
+            &mut engine,
+            "app:ui:button=click # primary action",
+            &config,
+        )
+        .unwrap();
+
+        assert_eq!(engine.get("app:ui:button"), Some("click"));
+    }
+
+    #[test]
+    fn test_process_with_config_hash_inside_quotes() {
+        let mut engine = MeteorEngine::new();
+        let config = ParserConfig::with_comments();
+
+        MeteorStreamParser::process_with_config(
+            &mut engine,
+            "app:ui:label=\"a # b\" # trailing note",
+            &config,
+        )
+        .unwrap();
+
+        let label = engine.get("app:ui:label").unwrap();
+        assert!(label.contains("a # b"));
+        assert!(!label.contains("trailing"));
+    }
+
+    #[test]
+    fn test_process_with_config_whole_line_comment() {
+        let mut engine = MeteorEngine::new();
+        let config = ParserConfig::with_comments();
+
+        MeteorStreamParser::process_with_config(
+            &mut engine,
+            "# ui settings\napp:ui:theme=dark :;:\n# user settings\nuser:main:lang=en",
+            &config,
+        )
+        .unwrap();
+
+        assert_eq!(engine.get("app:ui:theme"), Some("dark"));
+        assert_eq!(engine.get("user:main:lang"), Some("en"));
+    }
+
+    #[test]
+    fn test_comments_disabled_by_default() {
+        let mut engine = MeteorEngine::new();
+
+        MeteorStreamParser::process_with_config(
+            &mut engine,
+            "app:ui:button=click # primary action",
+            &ParserConfig::default(),
+        )
+        .unwrap();
+
+        assert_eq!(engine.get("app:ui:button"), Some("click # primary action"));
+    }
+
+    #[test]
+    fn test_process_with_spans_maps_tokens_to_input() {
+        let mut engine = MeteorEngine::new();
+        let input = "app:ui:button=click :;:  user:main:theme=dark";
+
+        let spans = MeteorStreamParser::process_with_spans(&mut engine, input).unwrap();
+
+        assert_eq!(
+            spans,
+            vec![
+                ("app:ui:button".to_string(), Span::new(0, 19)),
+                ("user:main:theme".to_string(), Span::new(25, 45)),
+            ]
+        );
+        assert_eq!(spans[1].1.slice(input), "user:main:theme=dark");
+        assert_eq!(engine.get("user:main:theme"), Some("dark"));
+    }
+
+    #[test]
+    fn test_process_lenient_skips_invalid_segments() {
+        let mut engine = MeteorEngine::new();
+        let input = "app:ui:button=click :;: app:ui:novalue :;: user:main:lang=en;user:main:tz=UTC :;: a:b:c:d=x";
+
+        let (applied, skipped) = MeteorStreamParser::process_lenient(&mut engine, input);
+
+        assert_eq!(applied, 3);
+        assert_eq!(engine.get("app:ui:button"), Some("click"));
+        assert_eq!(engine.get("user:main:lang"), Some("en"));
+        assert_eq!(engine.get("user:main:tz"), Some("UTC"));
+
+        let skipped_text: Vec<&str> = skipped.iter().map(|(span, _)| span.slice(input)).collect();
+        assert_eq!(skipped_text, vec!["app:ui:novalue", "a:b:c:d=x"]);
+        assert!(skipped[0]
+            .1
+            .to_string()
+            .contains("missing value assignment"));
+        assert!(skipped[1]
+            .1
+            .to_string()
+            .contains("expected context:namespace:key"));
+    }
+
+    #[test]
+    fn test_process_lenient_skipped_segment_changes_nothing() {
+        let mut engine = MeteorEngine::new();
+        engine.set("app:ui:old", "keep").unwrap();
+        engine.set_value_validator(|_, _, key, _| {
+            if key == "locked" {
+                Err("locked is read-only".to_string())
+            } else {
+                Ok(())
+            }
+        });
+        let input = "app:ui:ok=1 :;: app:ui:a=1;ctl:reset=storage;user:x:locked=2";
+
+        let (applied, skipped) = MeteorStreamParser::process_lenient(&mut engine, input);
+
+        assert_eq!(applied, 1);
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(
+            skipped[0].0.slice(input),
+            "app:ui:a=1;ctl:reset=storage;user:x:locked=2"
+        );
+        assert_eq!(engine.get("app:ui:a"), None);
+        assert_eq!(engine.get("app:ui:old"), Some("keep"));
+        assert_eq!(engine.get("app:ui:ok"), Some("1"));
+
+        // Positions in skipped errors refer to the whole input
+        let mut engine = MeteorEngine::new();
+        let input = "app:ui:ok=1 :;: app:ui:novalue";
+        let (_, skipped) = MeteorStreamParser::process_lenient(&mut engine, input);
+        assert_eq!(
+            skipped[0].1,
+            MeteorError::parse(
+                input.len(),
+                "Invalid meteor format: 'app:ui:novalue' - missing value assignment"
+            )
+        );
+    }
+
+    #[test]
+    fn test_bare_meteor_flags_with_config() {
+        let mut engine = MeteorEngine::new();
+        let input = "app:ui:verbose :;: app:ui:theme=dark";
+
+        assert!(MeteorStreamParser::process_with_config(
+            &mut engine,
+            input,
+            &ParserConfig::default()
+        )
+        .is_err());
+
+        let config = ParserConfig::with_bare_key_value("true");
+        MeteorStreamParser::process_with_config(&mut engine, input, &config).unwrap();
+        assert_eq!(engine.get("app:ui:verbose"), Some("true"));
+        assert_eq!(engine.get("app:ui:theme"), Some("dark"));
+    }
+
+    #[test]
+    fn test_analyze_counts_multi_segment_stream() {
+        let input = "app:ui:button=click;app:ui:theme=dark :;: ctl:reset=cursor :;: \
+                     user:main:lang=en;ctl:delete=app:old :;: ns=other :;: \
+                     app:ui:size=large;broken";
+        let analysis = MeteorStreamParser::analyze(input);
+
+        assert_eq!(
+            analysis,
+            StreamAnalysis {
+                segment_count: 5,
+                meteor_count: 2,
+                token_count: 4,
+                control_commands: 2,
+                namespaces_touched: vec!["app:ui".to_string(), "user:main".to_string()],
+                invalid_tokens: 1,
+            }
+        );
+        assert_eq!(MeteorStreamParser::analyze(""), StreamAnalysis::default());
+    }
+
+    #[test]
+    fn test_process_errors_report_stream_positions() {
+        let mut engine = MeteorEngine::new();
+        let input = "app:ui:button=click :;: app:ui:novalue";
+
+        let err = MeteorStreamParser::process(&mut engine, input).unwrap_err();
+        assert!(
+            err.starts_with(&format!("Parse error at position {}:", input.len())),
+            "{}",
+            err
+        );
+
+        let err = MeteorStreamParser::process(&mut engine, "app:ui:a=1; a:b:c:d=x").unwrap_err();
+        assert!(err.starts_with("Parse error at position 12:"), "{}", err);
+
+        let err = MeteorStreamParser::estimate(input).unwrap_err();
+        assert_eq!(
+            err,
+            MeteorError::parse(
+                input.len(),
+                "Invalid meteor format: 'app:ui:novalue' - missing value assignment"
+            )
+        );
+    }
+
+    #[test]
+    fn test_estimate_matches_stored_count() {
+        let streams = [
+            "app:ui:button=click",
+            "app:ui:button=click;app:ui:theme=dark :;: user:main:lang=en",
+            "app:ui:a=1 :;: ctl:reset=cursor :;: ns=other :;: app:main:b=2;ctl:reset=cursor",
+            "doc:guide:intro=\"a;b\" :;: doc:guide:list[0]=x;doc:guide:list[1]=y",
+            "",
+        ];
+
+        for input in streams {
+            let mut engine = MeteorEngine::new();
+            MeteorStreamParser::process(&mut engine, input).unwrap();
+            let stored = engine.iter_entries().count();
+            assert_eq!(
+                MeteorStreamParser::estimate(input).unwrap(),
+                stored,
+                "{}",
+                input
+            );
+            // Both preflights walk the stream the same way
+            let analysis = MeteorStreamParser::analyze(input);
+            assert_eq!(
+                analysis.token_count + analysis.invalid_tokens,
+                stored,
+                "{}",
+                input
+            );
+        }
+
+        assert!(MeteorStreamParser::estimate("app:ui:button=click;app:ui:flag").is_err());
+    }
 }
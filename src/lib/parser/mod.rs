@@ -29,14 +29,19 @@
 //! - Consistent error handling through MeteorError instead of String errors
 //! - Legacy methods preserved for backward compatibility
 
+pub mod config;
 pub mod escape;
 pub mod meteor_stream;
+pub mod span;
 pub mod split;
 pub mod token_stream;
 
-pub use escape::{parse_escaped_value, validate_escapes};
-pub use meteor_stream::MeteorStreamParser;
+pub use config::ParserConfig;
+pub use escape::{escape_delimiters, parse_escaped_value, unescape_delimiters, validate_escapes};
+pub use meteor_stream::{MeteorStreamParser, StreamAnalysis};
+pub use span::Span;
 pub use split::{
-    smart_split, smart_split_borrowed, smart_split_multi_char, smart_split_semicolons, SplitConfig,
+    join_continued_lines, smart_split, smart_split_borrowed, smart_split_multi_char,
+    smart_split_semicolons, strip_comments, SplitConfig,
 };
 pub use token_stream::TokenStreamParser;
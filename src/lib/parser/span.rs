@@ -0,0 +1,39 @@
+//! Source Spans - byte ranges of parsed items within stream input
+//!
+//! Spans let tooling map stored data back to the text it came from
+//! (e.g. "jump to definition" in editors).
+
+/// Half-open byte range `[start, end)` within the original input
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    /// Create a span covering `start..end`
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    /// Span of `part`, which must be a slice borrowed from `input`
+    pub(crate) fn of_slice(input: &str, part: &str) -> Self {
+        let start = part.as_ptr() as usize - input.as_ptr() as usize;
+        Self::new(start, start + part.len())
+    }
+
+    /// Length of the span in bytes
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Whether the span covers no bytes
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// The text this span covers in `input`
+    pub fn slice<'a>(&self, input: &'a str) -> &'a str {
+        &input[self.start..self.end]
+    }
+}
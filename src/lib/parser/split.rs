@@ -231,6 +231,93 @@ pub fn smart_split_semicolons(s: &str) -> Option<Vec<&str>> {
     Some(result)
 }
 
+/// Strip `#` comments from a stream, respecting quotes
+///
+/// A `#` starts a comment when it is outside quotes and either begins a line
+/// or follows whitespace; the comment runs to the end of the line. This keeps
+/// values such as `color=#fff` and `label="a # b"` intact.
+pub fn strip_comments(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut in_quotes = false;
+    let mut escape_next = false;
+    let mut in_comment = false;
+    let mut prev: Option<char> = None;
+
+    for ch in input.chars() {
+        if in_comment {
+            if ch == '\n' {
+                in_comment = false;
+                output.push(ch);
+            }
+            prev = Some(ch);
+            continue;
+        }
+
+        if escape_next {
+            escape_next = false;
+        } else {
+            match ch {
+                '\\' if in_quotes => escape_next = true,
+                '"' => in_quotes = !in_quotes,
+                '#' if !in_quotes && prev.is_none_or(char::is_whitespace) => {
+                    in_comment = true;
+                    prev = Some(ch);
+                    continue;
+                }
+                _ => {}
+            }
+        }
+
+        output.push(ch);
+        prev = Some(ch);
+    }
+
+    output
+}
+
+/// Join lines that end in a backslash continuation
+///
+/// An unquoted `\` immediately before a line break (`\n` or `\r\n`) is
+/// removed together with the break, so `key=line1\<newline>line2` becomes
+/// `key=line1line2`; no newline or space is inserted. Inside quotes the input
+/// is left untouched, so quoted values may contain literal newlines.
+pub fn join_continued_lines(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut in_quotes = false;
+    let mut chars = input.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' if in_quotes => {
+                output.push(ch);
+                if let Some(next) = chars.next() {
+                    output.push(next);
+                }
+            }
+            '\\' if chars.peek() == Some(&'\n') => {
+                chars.next();
+            }
+            '\\' if chars.peek() == Some(&'\r') => {
+                let mut lookahead = chars.clone();
+                lookahead.next();
+                if lookahead.peek() == Some(&'\n') {
+                    chars = lookahead;
+                    chars.next();
+                } else {
+                    output.push(ch);
+                }
+            }
+            '"' => {
+                in_quotes = !in_quotes;
+                output.push(ch);
+            }
+            _ => output.push(ch),
+        }
+    }
+
+    output
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -378,4 +465,34 @@ mod tests {
             assert_eq!(*b, o);
         }
     }
+
+    #[test]
+    fn test_strip_comments() {
+        assert_eq!(
+            strip_comments("app:ui:button=click # primary action").trim(),
+            "app:ui:button=click"
+        );
+        assert_eq!(
+            strip_comments("# header\napp:ui:theme=dark"),
+            "\napp:ui:theme=dark"
+        );
+        assert_eq!(strip_comments("label=\"a # b\" # note"), "label=\"a # b\" ");
+        assert_eq!(strip_comments("color=#fff"), "color=#fff");
+        assert_eq!(
+            strip_comments("msg=\"say \\\" # hi\" # gone"),
+            "msg=\"say \\\" # hi\" "
+        );
+    }
+
+    #[test]
+    fn test_join_continued_lines() {
+        assert_eq!(join_continued_lines("key=line1\\\nline2"), "key=line1line2");
+        assert_eq!(join_continued_lines("key=a\\\r\nb;x=1"), "key=ab;x=1");
+        assert_eq!(join_continued_lines("a=1\nb=2"), "a=1\nb=2");
+        // Quoted content is left untouched
+        assert_eq!(
+            join_continued_lines("msg=\"one\\\ntwo\""),
+            "msg=\"one\\\ntwo\""
+        );
+    }
 }
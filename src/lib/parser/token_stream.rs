@@ -7,9 +7,11 @@
 //! - Delegation to MeteorEngine for state changes
 //! - ENG-41: Meteor-aware parsing with aggregation and hardened constructors
 
-use crate::parser::split::{smart_split, SplitConfig};
+use crate::parser::config::ParserConfig;
+use crate::parser::split::{smart_split, smart_split_borrowed, SplitConfig};
 use crate::types::{Context, Meteor, MeteorEngine, MeteorError, Namespace, Token};
 use crate::utils::validators::is_valid_token_format;
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::str::FromStr;
 
@@ -31,8 +33,32 @@ impl TokenStreamParser {
         engine: &mut MeteorEngine,
         input: &str,
     ) -> Result<(), MeteorError> {
-        let (order, grouped_tokens) = Self::parse_and_group_tokens(engine, input)?;
-        Self::store_grouped_tokens(engine, order, grouped_tokens)
+        let (order, grouped_tokens) =
+            Self::parse_and_group_tokens(engine, input, &ParserConfig::default())?;
+        Self::store_grouped_tokens(engine, order, grouped_tokens, None)
+    }
+
+    /// Parse and process a stream with optional preprocessing from `ParserConfig`
+    ///
+    /// Applies the configured preprocessing (e.g. comment stripping) and then
+    /// behaves like `process_with_aggregation()`. With `bare_key_value` set,
+    /// flag tokens without `=` are stored with that value, and with
+    /// `max_tokens_per_meteor` set, larger groups fail with `TooManyTokens`.
+    ///
+    /// # Examples
+    /// ```ignore
+    /// let mut engine = MeteorEngine::new();
+    /// let config = ParserConfig::with_comments();
+    /// TokenStreamParser::process_with_config(&mut engine, "button=click # primary action", &config)?;
+    /// ```
+    pub fn process_with_config(
+        engine: &mut MeteorEngine,
+        input: &str,
+        config: &ParserConfig,
+    ) -> Result<(), MeteorError> {
+        let input = config.preprocess(input);
+        let (order, grouped_tokens) = Self::parse_and_group_tokens(engine, &input, config)?;
+        Self::store_grouped_tokens(engine, order, grouped_tokens, config.max_tokens_per_meteor)
     }
 
     /// Parse and process a token stream (Legacy method)
@@ -51,14 +77,17 @@ impl TokenStreamParser {
     /// ```
     pub fn process(engine: &mut MeteorEngine, input: &str) -> Result<(), String> {
         let (order, grouped_tokens) =
-            Self::parse_and_group_tokens(engine, input).map_err(|e| e.to_string())?;
-        Self::store_grouped_tokens(engine, order, grouped_tokens).map_err(|e| e.to_string())
+            Self::parse_and_group_tokens(engine, input, &ParserConfig::default())
+                .map_err(|e| e.to_string())?;
+        Self::store_grouped_tokens(engine, order, grouped_tokens, None).map_err(|e| e.to_string())
     }
 
+    /// Store each group as one meteor, rejecting groups past `token_limit`
     fn store_grouped_tokens(
         engine: &mut MeteorEngine,
         order: Vec<(Context, Namespace)>,
         mut grouped_tokens: HashMap<(Context, Namespace), Vec<Token>>,
+        token_limit: Option<usize>,
     ) -> Result<(), MeteorError> {
         for (context, namespace) in order {
             if let Some(tokens) = grouped_tokens.remove(&(context.clone(), namespace.clone())) {
@@ -66,8 +95,17 @@ impl TokenStreamParser {
                     continue;
                 }
 
-                let meteor =
-                    Meteor::try_new_with_tokens(context.clone(), namespace.clone(), tokens)?;
+                let meteor = match token_limit {
+                    Some(limit) => Meteor::try_new_with_token_limit(
+                        context.clone(),
+                        namespace.clone(),
+                        tokens,
+                        limit,
+                    )?,
+                    None => {
+                        Meteor::try_new_with_tokens(context.clone(), namespace.clone(), tokens)?
+                    }
+                };
                 Self::store_meteor_tokens(engine, &meteor)?;
             }
         }
@@ -104,6 +142,7 @@ impl TokenStreamParser {
     fn parse_and_group_tokens(
         engine: &mut MeteorEngine,
         input: &str,
+        config: &ParserConfig,
     ) -> Result<
         (
             Vec<(Context, Namespace)>,
@@ -113,7 +152,7 @@ impl TokenStreamParser {
     > {
         let mut grouped_tokens: HashMap<(Context, Namespace), Vec<Token>> = HashMap::new();
         let mut order: Vec<(Context, Namespace)> = Vec::new();
-        let parts = smart_split(input, SplitConfig::general_parsing(';'));
+        let parts = smart_split_borrowed(input, SplitConfig::general_parsing(';'));
 
         // Track current cursor state (mutable during parsing)
         let mut current_context = engine.current_context.clone();
@@ -131,6 +170,19 @@ impl TokenStreamParser {
                 continue;
             }
 
+            // Rebase token errors so positions are relative to the whole stream
+            let offset = part.as_ptr() as usize - input.as_ptr() as usize;
+            let expanded = config.expand_bare_flag(trimmed);
+            let (part, trimmed, offset) = match &expanded {
+                Cow::Owned(flag) => (
+                    flag.as_str(),
+                    flag.as_str(),
+                    trimmed.as_ptr() as usize - input.as_ptr() as usize,
+                ),
+                Cow::Borrowed(_) => (part, trimmed, offset),
+            };
+            let token = Token::from_str(part).map_err(|e| e.offset_by(offset))?;
+
             if !is_valid_token_format(trimmed) {
                 return Err(MeteorError::other(format!(
                     "Invalid token format: {}",
@@ -138,10 +190,6 @@ impl TokenStreamParser {
                 )));
             }
 
-            let token = Token::from_str(trimmed).map_err(|e| {
-                MeteorError::other(format!("Failed to parse token '{}': {}", trimmed, e))
-            })?;
-
             match token.key().transformed() {
                 "ns" => {
                     current_namespace = Namespace::from_string(token.value());
@@ -264,11 +312,12 @@ mod tests {
         engine.set("app.ui.button", "click").unwrap();
         TokenStreamParser::process(&mut engine, "ctl:delete=app.ui.button").unwrap();
 
-        // Check command was executed (though delete might not work due to StorageData limitations)
+        // The dotted target addresses app:ui:button
         let history = engine.command_history();
         assert!(history
             .iter()
             .any(|cmd| cmd.command_type == "delete" && cmd.target == "app.ui.button"));
+        assert_eq!(engine.get("app:ui:button"), None);
     }
 
     #[test]
@@ -277,4 +326,102 @@ mod tests {
         assert!(TokenStreamParser::validate("invalid format").is_err());
         assert!(TokenStreamParser::validate("key=\"value with; semicolons\"").is_ok());
     }
+
+    #[test]
+    fn test_process_with_config_comments() {
+        let mut engine = MeteorEngine::new();
+        let config = ParserConfig::with_comments();
+
+        TokenStreamParser::process_with_config(
+            &mut engine,
+            "# defaults\nbutton=click # primary action\n; ns=ui; label=\"#1 pick\"",
+            &config,
+        )
+        .unwrap();
+
+        assert_eq!(engine.get("app:main:button"), Some("click"));
+        assert!(engine.get("app:ui:label").unwrap().contains("#1 pick"));
+    }
+
+    #[test]
+    fn test_process_with_config_line_continuations() {
+        let mut engine = MeteorEngine::new();
+        let config = ParserConfig::with_line_continuations();
+
+        TokenStreamParser::process_with_config(
+            &mut engine,
+            "intro=line1\\\nline2;\nnote=\"first\nsecond\"",
+            &config,
+        )
+        .unwrap();
+
+        assert_eq!(engine.get("app:main:intro"), Some("line1line2"));
+        assert_eq!(engine.get("app:main:note"), Some("\"first\nsecond\""));
+    }
+
+    #[test]
+    fn test_token_errors_report_stream_offsets() {
+        let mut engine = MeteorEngine::new();
+
+        let err =
+            TokenStreamParser::process_with_aggregation(&mut engine, "a=1; =oops").unwrap_err();
+        assert!(matches!(err, MeteorError::ParseError { position: 5, .. }));
+
+        let err = TokenStreamParser::process_with_aggregation(&mut engine, "a=1;b=2;novalue")
+            .unwrap_err();
+        assert!(matches!(err, MeteorError::ParseError { position: 15, .. }));
+    }
+
+    #[test]
+    fn test_configured_token_limit_is_enforced() {
+        let mut engine = MeteorEngine::new();
+        let limit = crate::config::MAX_TOKENS_PER_METEOR;
+        let input = (0..=limit)
+            .map(|i| format!("k{}=v", i))
+            .collect::<Vec<_>>()
+            .join(";");
+
+        // No limit unless the config asks for one
+        TokenStreamParser::process_with_aggregation(&mut engine, &input).unwrap();
+        assert_eq!(engine.iter_entries().count(), limit + 1);
+
+        let mut engine = MeteorEngine::new();
+        let config = ParserConfig::with_token_limit(limit);
+        let err = TokenStreamParser::process_with_config(&mut engine, &input, &config).unwrap_err();
+        assert_eq!(
+            err,
+            MeteorError::TooManyTokens {
+                count: limit + 1,
+                limit
+            }
+        );
+        assert!(engine.is_empty());
+    }
+
+    #[test]
+    fn test_bare_flags_use_configured_value() {
+        let mut engine = MeteorEngine::new();
+        let config = ParserConfig::with_bare_key_value("true");
+
+        TokenStreamParser::process_with_config(&mut engine, "verbose;debug; level=2", &config)
+            .unwrap();
+
+        assert_eq!(engine.get("app:main:verbose"), Some("true"));
+        assert_eq!(engine.get("app:main:debug"), Some("true"));
+        assert_eq!(engine.get("app:main:level"), Some("2"));
+    }
+
+    #[test]
+    fn test_bare_flags_error_by_default() {
+        let mut engine = MeteorEngine::new();
+
+        assert!(TokenStreamParser::process_with_config(
+            &mut engine,
+            "verbose;debug",
+            &ParserConfig::default()
+        )
+        .is_err());
+        assert!(TokenStreamParser::process(&mut engine, "verbose;debug").is_err());
+        assert_eq!(engine.get("app:main:verbose"), None);
+    }
 }
@@ -1,5 +1,6 @@
 //! Context type - isolation boundaries for token data
 
+use std::borrow::Cow;
 use std::fmt;
 use std::str::FromStr;
 
@@ -14,28 +15,51 @@ use std::str::FromStr;
 /// - `remote1`, `remote2`, etc. - Remote source contexts
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Context {
-    name: String,
+    name: Cow<'static, str>,
 }
 
 impl Context {
+    /// The `app` context (default)
+    pub const APP: Context = Context::builtin("app");
+    /// The `user` context
+    pub const USER: Context = Context::builtin("user");
+    /// The `system` context
+    pub const SYSTEM: Context = Context::builtin("system");
+
+    const BUILTINS: [Context; 3] = [Context::APP, Context::USER, Context::SYSTEM];
+
+    const fn builtin(name: &'static str) -> Self {
+        Context {
+            name: Cow::Borrowed(name),
+        }
+    }
+
     /// Create a new context with the given name
+    ///
+    /// Built-in names share the static constants; custom names allocate.
     pub fn new(name: impl Into<String>) -> Self {
-        Context { name: name.into() }
+        let name = name.into();
+        match Self::BUILTINS.iter().find(|builtin| builtin.name == name) {
+            Some(builtin) => builtin.clone(),
+            None => Context {
+                name: Cow::Owned(name),
+            },
+        }
     }
 
     /// Create an app context (default)
     pub fn app() -> Self {
-        Context::new("app")
+        Self::APP
     }
 
     /// Create a user context
     pub fn user() -> Self {
-        Context::new("user")
+        Self::USER
     }
 
     /// Create a system context
     pub fn system() -> Self {
-        Context::new("system")
+        Self::SYSTEM
     }
 
     /// Get the context name
@@ -43,6 +67,14 @@ impl Context {
         &self.name
     }
 
+    /// Get the `&'static` name of a built-in context (`None` for custom contexts)
+    pub fn as_str(&self) -> Option<&'static str> {
+        match self.name {
+            Cow::Borrowed(name) => Some(name),
+            Cow::Owned(_) => None,
+        }
+    }
+
     /// Check if this is a privileged context (system or user)
     pub fn is_privileged(&self) -> bool {
         self.name == "system" || self.name == "user"
@@ -108,4 +140,23 @@ mod tests {
 
         assert!(Context::from_str("").is_err());
     }
+
+    #[test]
+    fn test_builtin_contexts_are_static() {
+        assert_eq!(Context::app(), Context::app());
+        assert_eq!(Context::user(), Context::USER);
+        assert_eq!(Context::system(), Context::new("system"));
+        assert_eq!(Context::default(), Context::APP);
+
+        assert_eq!(Context::app().as_str(), Some("app"));
+        assert_eq!(Context::USER.as_str(), Some("user"));
+        assert_eq!(
+            Context::from_str("system").unwrap().as_str(),
+            Some("system")
+        );
+
+        let custom = Context::new("file1");
+        assert_eq!(custom.name(), "file1");
+        assert_eq!(custom.as_str(), None);
+    }
 }
@@ -56,6 +56,19 @@ pub enum MeteorError {
         conflicting_namespaces: Vec<String>,
     },
 
+    /// Meteor token count exceeds the configured limit
+    TooManyTokens { count: usize, limit: usize },
+
+    /// Two distinct paths map to the same flattened key
+    KeyCollision {
+        key: String,
+        first: String,
+        second: String,
+    },
+
+    /// No value stored at a required path
+    NotFound { path: String },
+
     /// Generic error for other cases
     Other(String),
 }
@@ -123,6 +136,34 @@ impl MeteorError {
         }
     }
 
+    /// Create a missing-value error
+    pub fn not_found(path: impl Into<String>) -> Self {
+        MeteorError::NotFound { path: path.into() }
+    }
+
+    /// Shift a positional error by `base` bytes
+    ///
+    /// Used when an error was produced against a slice of a larger input, so the
+    /// reported position becomes relative to the whole input.
+    pub fn offset_by(self, base: usize) -> Self {
+        match self {
+            MeteorError::ParseError { position, message } => MeteorError::ParseError {
+                position: position + base,
+                message,
+            },
+            MeteorError::InvalidCharacter {
+                found,
+                position,
+                context,
+            } => MeteorError::InvalidCharacter {
+                found,
+                position: position + base,
+                context,
+            },
+            other => other,
+        }
+    }
+
     /// Create a generic error
     pub fn other(message: impl Into<String>) -> Self {
         MeteorError::Other(message.into())
@@ -146,6 +187,24 @@ impl MeteorError {
         }
     }
 
+    /// Create a too-many-tokens error
+    pub fn too_many_tokens(count: usize, limit: usize) -> Self {
+        MeteorError::TooManyTokens { count, limit }
+    }
+
+    /// Create a flattened key collision error
+    pub fn key_collision(
+        key: impl Into<String>,
+        first: impl Into<String>,
+        second: impl Into<String>,
+    ) -> Self {
+        MeteorError::KeyCollision {
+            key: key.into(),
+            first: first.into(),
+            second: second.into(),
+        }
+    }
+
     /// Create a mixed token namespaces error (ENG-40)
     pub fn mixed_token_namespaces(
         meteor_namespace: impl Into<String>,
@@ -231,6 +290,23 @@ impl fmt::Display for MeteorError {
                     conflicting_namespaces.join(", ")
                 )
             }
+            MeteorError::TooManyTokens { count, limit } => {
+                write!(
+                    f,
+                    "Meteor has {} tokens, exceeding the limit of {}",
+                    count, limit
+                )
+            }
+            MeteorError::KeyCollision { key, first, second } => {
+                write!(
+                    f,
+                    "Key collision: '{}' and '{}' both flatten to '{}'",
+                    first, second, key
+                )
+            }
+            MeteorError::NotFound { path } => {
+                write!(f, "No value found at '{}'", path)
+            }
             MeteorError::Other(message) => write!(f, "{}", message),
         }
     }
@@ -256,4 +332,13 @@ mod tests {
         let err = MeteorError::invalid_bracket("list[", "unclosed bracket");
         assert!(err.to_string().contains("Invalid bracket"));
     }
+
+    #[test]
+    fn test_offset_by_shifts_positions() {
+        let err = MeteorError::parse(3, "bad").offset_by(10);
+        assert!(matches!(err, MeteorError::ParseError { position: 13, .. }));
+
+        let err = MeteorError::empty("input").offset_by(10);
+        assert_eq!(err, MeteorError::empty("input"));
+    }
 }
@@ -36,6 +36,7 @@
 //! - **Type hints only** - export doesn't enforce, plugins use hints for smart behavior
 //! - **Extensible** - any bracket pattern works, these are just recognized conventions
 
+use std::collections::HashMap;
 use std::fmt;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -99,6 +100,81 @@ impl ContentType {
     pub fn is_canonical(&self) -> bool {
         matches!(self, ContentType::Canonical)
     }
+
+    /// Short name used for type hints in serialized exports
+    pub fn name(&self) -> &'static str {
+        match self {
+            ContentType::DocumentSection => "section",
+            ContentType::ScriptPart => "part",
+            ContentType::Chunk => "chunk",
+            ContentType::Function => "function",
+            ContentType::Library => "library",
+            ContentType::Module => "module",
+            ContentType::Blob => "blob",
+            ContentType::Metadata => "metadata",
+            ContentType::Canonical => "canonical",
+            ContentType::SimpleValue => "simple",
+        }
+    }
+
+    /// Parse a type hint name written by `name()`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "section" => Some(ContentType::DocumentSection),
+            "part" => Some(ContentType::ScriptPart),
+            "chunk" => Some(ContentType::Chunk),
+            "function" => Some(ContentType::Function),
+            "library" => Some(ContentType::Library),
+            "module" => Some(ContentType::Module),
+            "blob" => Some(ContentType::Blob),
+            "metadata" => Some(ContentType::Metadata),
+            "canonical" => Some(ContentType::Canonical),
+            "simple" => Some(ContentType::SimpleValue),
+            _ => None,
+        }
+    }
+}
+
+/// Pluggable checksum algorithm for export integrity metadata.
+///
+/// Implementors receive a canonical byte encoding of the exported context,
+/// namespace and tokens. The `name` is recorded as a prefix tag on the
+/// checksum (`name:digest`) so importers can validate with the matching
+/// algorithm. Untagged algorithms (empty `name`) receive the legacy encoding
+/// instead, each field followed by a `0xff` terminator, which is what
+/// `DefaultChecksum` hashed before algorithms were pluggable.
+pub trait ChecksumAlgorithm {
+    /// Short identifier recorded as the checksum prefix tag (e.g. `sha256`)
+    fn name(&self) -> &str;
+
+    /// Produce a digest string for the given bytes
+    fn digest(&self, bytes: &[u8]) -> String;
+}
+
+/// Built-in checksum algorithm (std `DefaultHasher`, base64 encoded).
+///
+/// Checksums produced by the built-in algorithm carry no prefix tag and are
+/// computed over the legacy field encoding, so they match exports written
+/// before pluggable algorithms existed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultChecksum;
+
+impl ChecksumAlgorithm for DefaultChecksum {
+    fn name(&self) -> &str {
+        ""
+    }
+
+    fn digest(&self, bytes: &[u8]) -> String {
+        use hub::data_ext::base64::{engine::general_purpose, Engine};
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::Hasher;
+
+        let mut hasher = DefaultHasher::new();
+        hasher.write(bytes);
+        let hash = hasher.finish();
+
+        general_purpose::STANDARD_NO_PAD.encode(hash.to_le_bytes())
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -137,6 +213,10 @@ pub struct ExportData {
     pub tokens: Vec<(String, String)>,
     pub metadata: ExportMetadata,
     pub format: ExportFormat,
+    /// Namespace annotations carried alongside the tokens (not checksummed)
+    pub annotations: Vec<(String, String)>,
+    /// Per-token content-type hints, serialized when present (not checksummed)
+    type_hints: HashMap<String, ContentType>,
 }
 
 impl ExportData {
@@ -146,7 +226,21 @@ impl ExportData {
         tokens: Vec<(String, String)>,
         format: ExportFormat,
     ) -> Self {
-        let checksum = Self::calculate_checksum(&context, &namespace, &tokens);
+        Self::new_with_hasher(context, namespace, tokens, format, &DefaultChecksum)
+    }
+
+    /// Create export data with a checksum produced by a custom algorithm
+    ///
+    /// The algorithm's `name()` is recorded as a prefix tag on
+    /// `metadata.checksum` so `verify_checksum` can detect mismatched algorithms.
+    pub fn new_with_hasher(
+        context: String,
+        namespace: String,
+        tokens: Vec<(String, String)>,
+        format: ExportFormat,
+        hasher: &dyn ChecksumAlgorithm,
+    ) -> Self {
+        let checksum = Self::calculate_checksum(&context, &namespace, &tokens, hasher);
         let metadata = ExportMetadata::new(checksum, tokens.len());
 
         Self {
@@ -155,24 +249,114 @@ impl ExportData {
             tokens,
             metadata,
             format,
+            annotations: Vec::new(),
+            type_hints: HashMap::new(),
         }
     }
 
-    fn calculate_checksum(context: &str, namespace: &str, tokens: &[(String, String)]) -> String {
-        use hub::data_ext::base64::{engine::general_purpose, Engine};
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
+    /// Attach namespace annotations to this export
+    pub fn with_annotations(mut self, annotations: Vec<(String, String)>) -> Self {
+        self.annotations = annotations;
+        self
+    }
 
-        let mut hasher = DefaultHasher::new();
-        context.hash(&mut hasher);
-        namespace.hash(&mut hasher);
+    /// Attach a content-type hint for every token, derived from its key
+    ///
+    /// Text exports gain one `# Type: key=name` line per token and JSON
+    /// tokens gain a `"type"` field, so importers need not re-derive them.
+    pub fn with_type_hints(mut self) -> Self {
+        self.type_hints = self
+            .tokens
+            .iter()
+            .map(|(key, _)| (key.clone(), ContentType::from_key(key)))
+            .collect();
+        self
+    }
+
+    /// Type hint carried for `key`, if the export has one
+    pub fn type_hint(&self, key: &str) -> Option<&ContentType> {
+        self.type_hints.get(key)
+    }
+
+    /// All carried type hints, keyed by token key
+    pub fn type_hints(&self) -> &HashMap<String, ContentType> {
+        &self.type_hints
+    }
+
+    /// Name of the algorithm that produced `metadata.checksum`
+    ///
+    /// Returns an empty string for untagged (built-in) checksums.
+    pub fn checksum_algorithm(&self) -> &str {
+        match self.metadata.checksum.split_once(':') {
+            Some((tag, _)) => tag,
+            None => "",
+        }
+    }
+
+    /// Recompute the checksum with `hasher` and compare it to the stored one
+    ///
+    /// Fails if the stored checksum was tagged by a different algorithm.
+    pub fn verify_checksum(&self, hasher: &dyn ChecksumAlgorithm) -> bool {
+        self.checksum_algorithm() == hasher.name()
+            && Self::calculate_checksum(&self.context, &self.namespace, &self.tokens, hasher)
+                == self.metadata.checksum
+    }
+
+    fn calculate_checksum(
+        context: &str,
+        namespace: &str,
+        tokens: &[(String, String)],
+        hasher: &dyn ChecksumAlgorithm,
+    ) -> String {
+        if hasher.name().is_empty() {
+            hasher.digest(&Self::legacy_checksum_payload(context, namespace, tokens))
+        } else {
+            let digest = hasher.digest(&Self::checksum_payload(context, namespace, tokens));
+            format!("{}:{}", hasher.name(), digest)
+        }
+    }
+
+    /// Field encoding used by untagged checksums: each field's bytes then `0xff`
+    ///
+    /// Matches how `str` feeds a `Hasher`, so `DefaultChecksum` reproduces
+    /// checksums of exports written before algorithms were pluggable.
+    fn legacy_checksum_payload(
+        context: &str,
+        namespace: &str,
+        tokens: &[(String, String)],
+    ) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let mut push_field = |field: &str| {
+            bytes.extend_from_slice(field.as_bytes());
+            bytes.push(0xff);
+        };
+
+        push_field(context);
+        push_field(namespace);
         for (key, value) in tokens {
-            key.hash(&mut hasher);
-            value.hash(&mut hasher);
+            push_field(key);
+            push_field(value);
         }
-        let hash = hasher.finish();
 
-        general_purpose::STANDARD_NO_PAD.encode(hash.to_le_bytes())
+        bytes
+    }
+
+    /// Canonical byte encoding of the checksummed fields (length-prefixed)
+    fn checksum_payload(context: &str, namespace: &str, tokens: &[(String, String)]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let mut push_field = |field: &str| {
+            bytes.extend_from_slice(&(field.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(field.as_bytes());
+        };
+
+        push_field(context);
+        push_field(namespace);
+        for (key, value) in tokens {
+            push_field(key);
+            push_field(value);
+        }
+
+        bytes
     }
 
     pub fn to_text(&self) -> String {
@@ -183,7 +367,21 @@ impl ExportData {
         output.push_str(&format!("# Namespace: {}\n", self.namespace));
         output.push_str(&format!("# Checksum: {}\n", self.metadata.checksum));
         output.push_str(&format!("# Timestamp: {}\n", self.metadata.timestamp));
-        output.push_str(&format!("# Token Count: {}\n\n", self.metadata.token_count));
+        output.push_str(&format!("# Token Count: {}\n", self.metadata.token_count));
+        for (key, value) in &self.annotations {
+            output.push_str(&format!(
+                "# Meta: {}={}\n",
+                Self::escape_meta(key),
+                Self::escape_meta(value)
+            ));
+        }
+        // Hints follow token order so the output is deterministic
+        for (key, _) in &self.tokens {
+            if let Some(content_type) = self.type_hint(key) {
+                output.push_str(&format!("# Type: {}={}\n", key, content_type.name()));
+            }
+        }
+        output.push('\n');
 
         for (key, value) in &self.tokens {
             output.push_str(&format!("{}={}\n", key, value));
@@ -197,15 +395,35 @@ impl ExportData {
         output.push_str("{\n");
         output.push_str(&format!("  \"context\": \"{}\",\n", self.context));
         output.push_str(&format!("  \"namespace\": \"{}\",\n", self.namespace));
+        if !self.annotations.is_empty() {
+            let annotations: serde_json::Map<String, serde_json::Value> = self
+                .annotations
+                .iter()
+                .map(|(key, value)| (key.clone(), serde_json::Value::String(value.clone())))
+                .collect();
+            output.push_str(&format!(
+                "  \"annotations\": {},\n",
+                serde_json::Value::Object(annotations)
+            ));
+        }
         output.push_str("  \"tokens\": [\n");
 
         for (i, (key, value)) in self.tokens.iter().enumerate() {
             output.push_str("    {\n");
             output.push_str(&format!("      \"key\": \"{}\",\n", Self::escape_json(key)));
-            output.push_str(&format!(
-                "      \"value\": \"{}\"\n",
-                Self::escape_json(value)
-            ));
+            match self.type_hint(key) {
+                Some(content_type) => {
+                    output.push_str(&format!(
+                        "      \"value\": \"{}\",\n",
+                        Self::escape_json(value)
+                    ));
+                    output.push_str(&format!("      \"type\": \"{}\"\n", content_type.name()));
+                }
+                None => output.push_str(&format!(
+                    "      \"value\": \"{}\"\n",
+                    Self::escape_json(value)
+                )),
+            }
             if i < self.tokens.len() - 1 {
                 output.push_str("    },\n");
             } else {
@@ -247,6 +465,8 @@ impl ExportData {
         let mut checksum = String::new();
         let mut timestamp = 0u64;
         let mut tokens = Vec::new();
+        let mut annotations = Vec::new();
+        let mut type_hints = HashMap::new();
 
         for line in text.lines() {
             let line = line.trim();
@@ -270,6 +490,17 @@ impl ExportData {
                 timestamp = ts_str
                     .parse()
                     .map_err(|_| format!("Invalid timestamp: {}", ts_str))?;
+            } else if let Some(meta) = line.strip_prefix("# Meta:") {
+                if let Some(annotation) = Self::parse_meta(meta.trim()) {
+                    annotations.push(annotation);
+                }
+            } else if let Some(hint) = line.strip_prefix("# Type:") {
+                // Unknown type names are skipped so newer exports still load
+                if let Some((key, name)) = hint.trim().rsplit_once('=') {
+                    if let Some(content_type) = ContentType::from_name(name) {
+                        type_hints.insert(key.to_string(), content_type);
+                    }
+                }
             } else if line.starts_with('#') {
                 continue;
             } else if let Some(eq_pos) = line.find('=') {
@@ -295,13 +526,22 @@ impl ExportData {
             tokens,
             metadata,
             format: ExportFormat::Text,
+            annotations,
+            type_hints,
         })
     }
 
+    /// Content type of every token, preferring carried type hints
     pub fn content_types(&self) -> Vec<(String, ContentType)> {
         self.tokens
             .iter()
-            .map(|(key, _)| (key.clone(), ContentType::from_key(key)))
+            .map(|(key, _)| {
+                let content_type = self
+                    .type_hint(key)
+                    .cloned()
+                    .unwrap_or_else(|| ContentType::from_key(key));
+                (key.clone(), content_type)
+            })
             .collect()
     }
 
@@ -332,6 +572,7 @@ impl ExportData {
 
         let context = Self::extract_json_string(json, "context")?;
         let namespace = Self::extract_json_string(json, "namespace")?;
+        let annotations = Self::extract_json_annotations(json)?;
 
         let tokens_start = json.find("\"tokens\"").ok_or("Missing 'tokens' field")?;
         let tokens_section = &json[tokens_start..];
@@ -340,6 +581,7 @@ impl ExportData {
         let tokens_array = &tokens_section[array_start + 1..array_end];
 
         let mut tokens = Vec::new();
+        let mut type_hints = HashMap::new();
         for token_obj in tokens_array.split("},") {
             let token_obj = token_obj.trim();
             if token_obj.is_empty() {
@@ -348,6 +590,12 @@ impl ExportData {
 
             let key = Self::extract_json_string(token_obj, "key")?;
             let value = Self::extract_json_string(token_obj, "value")?;
+            if let Some(content_type) = Self::extract_json_string(token_obj, "type")
+                .ok()
+                .and_then(|name| ContentType::from_name(&name))
+            {
+                type_hints.insert(key.clone(), content_type);
+            }
             tokens.push((key, value));
         }
 
@@ -367,9 +615,32 @@ impl ExportData {
             tokens,
             metadata,
             format: ExportFormat::Json,
+            annotations,
+            type_hints,
         })
     }
 
+    /// Parse the optional `"annotations"` object written by `to_json`
+    fn extract_json_annotations(json: &str) -> Result<Vec<(String, String)>, String> {
+        let Some(start) = json.find("\"annotations\": ") else {
+            return Ok(Vec::new());
+        };
+        let rest = &json[start + "\"annotations\": ".len()..];
+        let object = serde_json::Deserializer::from_str(rest)
+            .into_iter::<serde_json::Map<String, serde_json::Value>>()
+            .next()
+            .ok_or("Missing annotations object")?
+            .map_err(|e| format!("Malformed field: annotations ({})", e))?;
+
+        Ok(object
+            .into_iter()
+            .map(|(key, value)| match value {
+                serde_json::Value::String(text) => (key, text),
+                other => (key, other.to_string()),
+            })
+            .collect())
+    }
+
     fn extract_json_string(json: &str, field: &str) -> Result<String, String> {
         let pattern = format!("\"{}\": \"", field);
         let start = json
@@ -401,6 +672,44 @@ impl ExportData {
             .map_err(|_| format!("Invalid number for field: {}", field))
     }
 
+    /// Escape an annotation field so it stays on one `# Meta:` line
+    ///
+    /// Backslashes, line breaks and `=` are backslash-escaped; the first
+    /// unescaped `=` on the line separates key from value.
+    fn escape_meta(s: &str) -> String {
+        let mut escaped = String::with_capacity(s.len());
+        for c in s.chars() {
+            match c {
+                '\\' => escaped.push_str("\\\\"),
+                '\n' => escaped.push_str("\\n"),
+                '\r' => escaped.push_str("\\r"),
+                '=' => escaped.push_str("\\="),
+                other => escaped.push(other),
+            }
+        }
+        escaped
+    }
+
+    /// Split and unescape a `key=value` annotation written by `escape_meta`
+    fn parse_meta(s: &str) -> Option<(String, String)> {
+        let mut key = None;
+        let mut current = String::new();
+        let mut chars = s.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some('n') => current.push('\n'),
+                    Some('r') => current.push('\r'),
+                    Some(other) => current.push(other),
+                    None => current.push('\\'),
+                },
+                '=' if key.is_none() => key = Some(std::mem::take(&mut current)),
+                other => current.push(other),
+            }
+        }
+        key.map(|key| (key, current))
+    }
+
     fn unescape_json(s: &str) -> String {
         s.replace("\\n", "\n")
             .replace("\\r", "\r")
@@ -436,6 +745,56 @@ pub enum ImportDiff {
     Unchanged {
         key: String,
     },
+    Removed {
+        key: String,
+        value: String,
+    },
+}
+
+impl ImportDiff {
+    /// Classify one key given its old value (if any) and its new value
+    pub(crate) fn classify(key: &str, old_value: Option<&str>, new_value: &str) -> Self {
+        match old_value {
+            Some(old) if old == new_value => ImportDiff::Unchanged {
+                key: key.to_string(),
+            },
+            Some(old) => ImportDiff::Updated {
+                key: key.to_string(),
+                old_value: old.to_string(),
+                new_value: new_value.to_string(),
+            },
+            None => ImportDiff::Added {
+                key: key.to_string(),
+                value: new_value.to_string(),
+            },
+        }
+    }
+
+    /// Rewrite the key as `prefix` + key, keeping the change itself
+    pub(crate) fn with_key_prefix(self, prefix: &str) -> Self {
+        match self {
+            ImportDiff::Added { key, value } => ImportDiff::Added {
+                key: format!("{}{}", prefix, key),
+                value,
+            },
+            ImportDiff::Updated {
+                key,
+                old_value,
+                new_value,
+            } => ImportDiff::Updated {
+                key: format!("{}{}", prefix, key),
+                old_value,
+                new_value,
+            },
+            ImportDiff::Unchanged { key } => ImportDiff::Unchanged {
+                key: format!("{}{}", prefix, key),
+            },
+            ImportDiff::Removed { key, value } => ImportDiff::Removed {
+                key: format!("{}{}", prefix, key),
+                value,
+            },
+        }
+    }
 }
 
 impl fmt::Display for ImportDiff {
@@ -448,6 +807,7 @@ impl fmt::Display for ImportDiff {
                 new_value,
             } => write!(f, "~ {}: {} → {}", key, old_value, new_value),
             ImportDiff::Unchanged { key } => write!(f, "  {}", key),
+            ImportDiff::Removed { key, value } => write!(f, "- {}: {}", key, value),
         }
     }
 }
@@ -505,3 +865,254 @@ impl fmt::Display for ImportResult {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// FNV-1a (64-bit), hex encoded
+    struct Fnv1a;
+
+    impl ChecksumAlgorithm for Fnv1a {
+        fn name(&self) -> &str {
+            "fnv1a"
+        }
+
+        fn digest(&self, bytes: &[u8]) -> String {
+            let mut hash: u64 = 0xcbf29ce484222325;
+            for byte in bytes {
+                hash ^= *byte as u64;
+                hash = hash.wrapping_mul(0x100000001b3);
+            }
+            format!("{:016x}", hash)
+        }
+    }
+
+    /// Adler-32, hex encoded
+    struct Adler32;
+
+    impl ChecksumAlgorithm for Adler32 {
+        fn name(&self) -> &str {
+            "adler32"
+        }
+
+        fn digest(&self, bytes: &[u8]) -> String {
+            let (mut a, mut b) = (1u32, 0u32);
+            for byte in bytes {
+                a = (a + *byte as u32) % 65521;
+                b = (b + a) % 65521;
+            }
+            format!("{:08x}", (b << 16) | a)
+        }
+    }
+
+    fn sample_tokens() -> Vec<(String, String)> {
+        vec![
+            ("section[intro]".to_string(), "Welcome".to_string()),
+            ("section[body]".to_string(), "Content".to_string()),
+        ]
+    }
+
+    fn export_with(hasher: &dyn ChecksumAlgorithm) -> ExportData {
+        ExportData::new_with_hasher(
+            "doc".to_string(),
+            "guide".to_string(),
+            sample_tokens(),
+            ExportFormat::Text,
+            hasher,
+        )
+    }
+
+    #[test]
+    fn test_custom_hashers_tag_and_differ() {
+        let fnv = export_with(&Fnv1a);
+        let adler = export_with(&Adler32);
+
+        assert!(fnv.metadata.checksum.starts_with("fnv1a:"));
+        assert!(adler.metadata.checksum.starts_with("adler32:"));
+        assert_eq!(fnv.checksum_algorithm(), "fnv1a");
+        assert_eq!(adler.checksum_algorithm(), "adler32");
+        assert_ne!(fnv.metadata.checksum, adler.metadata.checksum);
+
+        // Each hasher is deterministic
+        assert_eq!(fnv.metadata.checksum, export_with(&Fnv1a).metadata.checksum);
+        assert_eq!(
+            adler.metadata.checksum,
+            export_with(&Adler32).metadata.checksum
+        );
+    }
+
+    #[test]
+    fn test_verify_checksum_requires_matching_algorithm() {
+        let fnv = export_with(&Fnv1a);
+        assert!(fnv.verify_checksum(&Fnv1a));
+        assert!(!fnv.verify_checksum(&Adler32));
+        assert!(!fnv.verify_checksum(&DefaultChecksum));
+
+        let mut tampered = fnv.clone();
+        tampered.tokens[0].1 = "Tampered".to_string();
+        assert!(!tampered.verify_checksum(&Fnv1a));
+    }
+
+    #[test]
+    fn test_default_checksum_is_untagged() {
+        let export = ExportData::new(
+            "doc".to_string(),
+            "guide".to_string(),
+            sample_tokens(),
+            ExportFormat::Text,
+        );
+        assert_eq!(export.checksum_algorithm(), "");
+        assert!(export.verify_checksum(&DefaultChecksum));
+        assert_eq!(
+            export.metadata.checksum,
+            export_with(&DefaultChecksum).metadata.checksum
+        );
+    }
+
+    #[test]
+    fn test_tagged_checksum_survives_text_round_trip() {
+        let export = export_with(&Adler32);
+        let parsed = ExportData::from_text(&export.to_text()).unwrap();
+        assert_eq!(parsed.metadata.checksum, export.metadata.checksum);
+        assert!(parsed.verify_checksum(&Adler32));
+    }
+
+    #[test]
+    fn test_import_validates_with_matching_hasher() {
+        use crate::types::MeteorEngine;
+
+        let mut source = MeteorEngine::new();
+        source.set("doc:guide:section[intro]", "Welcome").unwrap();
+        let export = source
+            .export_namespace_with_hasher("doc", "guide", ExportFormat::Text, &Fnv1a)
+            .unwrap();
+
+        let mut matching = MeteorEngine::new();
+        let result = matching
+            .import_namespace_with_hasher(export.clone(), &Fnv1a)
+            .unwrap();
+        assert!(result.checksum_valid);
+
+        let mut mismatched = MeteorEngine::new();
+        let result = mismatched.import_namespace(export.clone()).unwrap();
+        assert!(result.success);
+        assert!(!result.checksum_valid);
+
+        // Sandboxed imports check the source checksum with the given algorithm
+        let mut sandboxed = MeteorEngine::new();
+        let result = sandboxed
+            .import_namespace_prefixed_with_hasher(export.clone(), "imports", "vendor", &Fnv1a)
+            .unwrap();
+        assert!(result.checksum_valid);
+        assert_eq!(
+            sandboxed.get("imports:vendor.guide:section[intro]"),
+            Some("Welcome")
+        );
+        let result = sandboxed
+            .import_namespace_prefixed(export, "imports", "other")
+            .unwrap();
+        assert!(!result.checksum_valid);
+    }
+
+    #[test]
+    fn test_default_checksum_matches_legacy_exports() {
+        use hub::data_ext::base64::{engine::general_purpose, Engine};
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        // The checksum as computed before algorithms were pluggable
+        let mut hasher = DefaultHasher::new();
+        "doc".hash(&mut hasher);
+        "guide".hash(&mut hasher);
+        for (key, value) in sample_tokens() {
+            key.hash(&mut hasher);
+            value.hash(&mut hasher);
+        }
+        let legacy = general_purpose::STANDARD_NO_PAD.encode(hasher.finish().to_le_bytes());
+
+        let export = ExportData::new(
+            "doc".to_string(),
+            "guide".to_string(),
+            sample_tokens(),
+            ExportFormat::Text,
+        );
+        assert_eq!(export.metadata.checksum, legacy);
+        assert!(export.verify_checksum(&DefaultChecksum));
+    }
+
+    #[test]
+    fn test_annotations_survive_json_round_trip() {
+        let export = export_with(&DefaultChecksum).with_annotations(vec![
+            ("owner".to_string(), "docs \"team\"".to_string()),
+            ("stage".to_string(), "draft".to_string()),
+        ]);
+        let parsed = ExportData::from_json(&export.to_json().unwrap()).unwrap();
+        assert_eq!(parsed.annotations, export.annotations);
+        assert_eq!(parsed.tokens, sample_tokens());
+        assert!(parsed.verify_checksum(&DefaultChecksum));
+
+        let bare =
+            ExportData::from_json(&export_with(&DefaultChecksum).to_json().unwrap()).unwrap();
+        assert!(bare.annotations.is_empty());
+    }
+
+    #[test]
+    fn test_annotations_survive_text_round_trip() {
+        let export = export_with(&DefaultChecksum).with_annotations(vec![
+            ("owner".to_string(), "docs\nteam".to_string()),
+            ("a=b".to_string(), "x=y \\ z".to_string()),
+            ("stage".to_string(), "draft".to_string()),
+        ]);
+        let text = export.to_text();
+        assert!(text.contains("# Meta: owner=docs\\nteam\n"));
+
+        let parsed = ExportData::from_text(&text).unwrap();
+        assert_eq!(parsed.annotations, export.annotations);
+        assert_eq!(parsed.tokens, sample_tokens());
+    }
+
+    #[test]
+    fn test_type_hints_round_trip_text_and_json() {
+        let mut tokens = sample_tokens();
+        tokens.push(("port".to_string(), "8080".to_string()));
+        let export = ExportData::new(
+            "doc".to_string(),
+            "guide".to_string(),
+            tokens,
+            ExportFormat::Text,
+        )
+        .with_type_hints();
+
+        let text = export.to_text();
+        assert!(text.contains("# Type: section[intro]=section\n"));
+        assert!(text.contains("# Type: port=simple\n"));
+        let from_text = ExportData::from_text(&text).unwrap();
+        assert_eq!(from_text.type_hints(), export.type_hints());
+        assert_eq!(from_text.tokens, export.tokens);
+
+        let json = export.to_json().unwrap();
+        assert!(json.contains("\"type\": \"section\""));
+        let from_json = ExportData::from_json(&json).unwrap();
+        assert_eq!(from_json.type_hints(), export.type_hints());
+        assert_eq!(from_json.tokens, export.tokens);
+
+        // Hints are not checksummed, so parsed exports still verify
+        assert!(from_text.verify_checksum(&DefaultChecksum));
+        assert!(from_json.verify_checksum(&DefaultChecksum));
+    }
+
+    #[test]
+    fn test_content_types_prefer_hints() {
+        let text = "# Context: doc\n# Namespace: guide\n# Type: body=blob\n# Type: port=unknown\n\nbody=data\nport=8080\n";
+        let export = ExportData::from_text(text).unwrap();
+
+        assert_eq!(
+            export.content_types(),
+            vec![
+                ("body".to_string(), ContentType::Blob),
+                ("port".to_string(), ContentType::SimpleValue),
+            ]
+        );
+    }
+}
@@ -1,5 +1,7 @@
 //! Meteor type - the complete token addressing structure
 
+use crate::parser::escape::{escape_delimiters, unescape_delimiters};
+use crate::parser::split::{smart_split_borrowed, SplitConfig};
 use crate::types::{Context, MeteorError, Namespace, Token};
 use std::fmt;
 use std::str::FromStr;
@@ -243,6 +245,21 @@ impl Meteor {
         })
     }
 
+    /// Like `try_new_with_tokens()`, but with an explicit maximum token count
+    ///
+    /// Returns `MeteorError::TooManyTokens` when `tokens.len() > limit`.
+    pub fn try_new_with_token_limit(
+        context: Context,
+        namespace: Namespace,
+        tokens: Vec<Token>,
+        limit: usize,
+    ) -> Result<Self, MeteorError> {
+        if tokens.len() > limit {
+            return Err(MeteorError::too_many_tokens(tokens.len(), limit));
+        }
+        Self::try_new_with_tokens(context, namespace, tokens)
+    }
+
     /// Try to create with default context (ENG-40: Safe constructor)
     ///
     /// Returns `Err` if the token's namespace doesn't match the meteor's namespace.
@@ -345,6 +362,34 @@ impl Meteor {
         }
     }
 
+    /// Assemble a meteor from tokens already validated for `namespace`
+    ///
+    /// Crate-internal: used when combining meteors whose tokens passed
+    /// validation at construction, so re-validation is unnecessary.
+    pub(crate) fn from_validated_tokens(
+        context: Context,
+        namespace: Namespace,
+        tokens: Vec<Token>,
+    ) -> Self {
+        Meteor {
+            context,
+            namespace,
+            tokens,
+        }
+    }
+
+    /// Re-run hardened constructor validation against this meteor
+    ///
+    /// Useful for meteors from untrusted sources or built via the deprecated
+    /// unchecked constructors. Applies the same checks as `try_new_with_tokens()`.
+    pub fn validate(&self) -> Result<(), MeteorError> {
+        if self.tokens.is_empty() {
+            return Err(MeteorError::EmptyTokens);
+        }
+
+        Self::validate_tokens_strict(&self.namespace, &self.tokens)
+    }
+
     /// Get the context
     pub fn context(&self) -> &Context {
         &self.context
@@ -365,13 +410,62 @@ impl Meteor {
         &self.tokens
     }
 
+    /// Render only the token portion: "key=value;key2=value2"
+    ///
+    /// Keys use notation form and no context/namespace prefix is included, so
+    /// the result can be fed straight to `TokenStreamParser`.
+    pub fn tokens_string(&self) -> String {
+        self.tokens
+            .iter()
+            .map(|token| format!("{}={}", token.key_notation(), token.value()))
+            .collect::<Vec<_>>()
+            .join(";")
+    }
+
+    /// Render a string that always round-trips through `Meteor::parse_canonical`
+    ///
+    /// Unlike `Display`, delimiters (`;`, `:`, `=`), quotes and backslashes
+    /// inside the context, namespace, keys and values are backslash-escaped,
+    /// so values such as `a;b` or `x=y` survive re-parsing unchanged.
+    pub fn to_canonical_string(&self) -> String {
+        let tokens = self
+            .tokens
+            .iter()
+            .map(|token| {
+                format!(
+                    "{}={}",
+                    escape_delimiters(token.key_notation()),
+                    escape_delimiters(token.value())
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(";");
+
+        format!(
+            "{}:{}:{}",
+            escape_delimiters(self.context.name()),
+            escape_delimiters(&self.namespace.to_string()),
+            tokens
+        )
+    }
+
     /// Parse from full format: "context:namespace:key=value;key2=value2"
     /// Returns Vec<Meteor> to support multiple meteor specifications
+    ///
+    /// Token errors report their byte position within the whole input.
     pub fn parse(s: &str) -> Result<Vec<Self>, String> {
-        let meteor = Self::parse_single(s)?;
+        let meteor = Self::parse_single(s, false).map_err(|e| e.to_string())?;
         Ok(vec![meteor])
     }
 
+    /// Parse a string produced by `to_canonical_string`
+    ///
+    /// Unlike `parse`, backslash escapes are honoured: escaped delimiters and
+    /// quotes do not split the input and are unescaped in the result.
+    pub fn parse_canonical(s: &str) -> Result<Self, String> {
+        Self::parse_single(s, true).map_err(|e| e.to_string())
+    }
+
     /// Parse the first meteor from a string (convenience method)
     pub fn first(s: &str) -> Result<Self, String> {
         let meteors = Self::parse(s)?;
@@ -379,66 +473,159 @@ impl Meteor {
     }
 
     /// Parse a single meteor from a string (internal method)
-    fn parse_single(s: &str) -> Result<Self, String> {
-        // Count colons to determine format
-        let colon_count = s.chars().filter(|&c| c == ':').count();
+    ///
+    /// With `canonical` set, backslash escapes are honoured and removed.
+    fn parse_single(s: &str, canonical: bool) -> Result<Self, MeteorError> {
+        // Count colons (unescaped ones in canonical form) to determine format
+        let colons: Vec<usize> = if canonical {
+            unescaped_positions(s, ':')
+        } else {
+            s.match_indices(':').map(|(pos, _)| pos).collect()
+        };
+        let component = |part: &str| {
+            if canonical {
+                unescape_delimiters(part)
+            } else {
+                part.to_string()
+            }
+        };
 
-        match colon_count {
+        match colons.len() {
             0 => {
                 // No context or namespace, just token(s) - use default namespace
-                let tokens = Self::parse_tokens(s)?;
+                let tokens = Self::parse_tokens(s, 0, canonical)?;
                 Self::from_parts(Context::default(), Namespace::default(), tokens)
+                    .map_err(MeteorError::other)
             }
             1 => {
                 // Format: namespace:token(s)
-                let parts: Vec<&str> = s.splitn(2, ':').collect();
+                let parts = [&s[..colons[0]], &s[colons[0] + 1..]];
 
                 // Check if second part contains '='
                 if parts[1].contains('=') {
                     // Assume first part is namespace (no context specified)
-                    let namespace = Namespace::from_string(parts[0]);
-                    let tokens = Self::parse_tokens(parts[1])?;
+                    let namespace = Namespace::from_string(&component(parts[0]));
+                    let tokens = Self::parse_tokens(parts[1], colons[0] + 1, canonical)?;
                     Self::from_parts(Context::default(), namespace, tokens)
+                        .map_err(MeteorError::other)
                 } else {
-                    Err(format!("Invalid meteor format: {}", s))
+                    Err(MeteorError::other(format!("Invalid meteor format: {}", s)))
                 }
             }
             2 => {
                 // Full format: context:namespace:token(s)
-                let parts: Vec<&str> = s.splitn(3, ':').collect();
-
-                let context = Context::from_str(parts[0])?;
-                let namespace = Namespace::from_string(parts[1]);
-                let tokens = Self::parse_tokens(parts[2])?;
-
-                Self::from_parts(context, namespace, tokens)
+                let parts = [
+                    &s[..colons[0]],
+                    &s[colons[0] + 1..colons[1]],
+                    &s[colons[1] + 1..],
+                ];
+
+                let context =
+                    Context::from_str(&component(parts[0])).map_err(MeteorError::other)?;
+                let namespace = Namespace::from_string(&component(parts[1]));
+                let tokens = Self::parse_tokens(parts[2], colons[1] + 1, canonical)?;
+
+                Self::from_parts(context, namespace, tokens).map_err(MeteorError::other)
             }
-            _ => Err(format!("Too many colons in meteor format: {}", s)),
+            _ => Err(MeteorError::other(format!(
+                "Too many colons in meteor format: {}",
+                s
+            ))),
         }
     }
 
     /// Parse semicolon-separated tokens
-    fn parse_tokens(tokens_str: &str) -> Result<Vec<Token>, String> {
-        let parts = crate::parser::split::smart_split_semicolons(tokens_str)
-            .ok_or_else(|| "Unbalanced quotes in token string".to_string())?;
+    ///
+    /// In canonical form, backslash escapes (as produced by
+    /// `to_canonical_string`) protect delimiters and quotes from splitting.
+    /// `base` is the byte offset of `tokens_str` within the full meteor string,
+    /// so error positions refer to the whole input.
+    fn parse_tokens(
+        tokens_str: &str,
+        base: usize,
+        canonical: bool,
+    ) -> Result<Vec<Token>, MeteorError> {
+        let quotes: Vec<usize> = if canonical {
+            unescaped_positions(tokens_str, '"')
+        } else {
+            tokens_str.match_indices('"').map(|(pos, _)| pos).collect()
+        };
+        if quotes.len() % 2 != 0 {
+            return Err(MeteorError::parse(
+                base + quotes.last().copied().unwrap_or(0),
+                "Unbalanced quotes in token string",
+            ));
+        }
+        let config = if canonical {
+            SplitConfig {
+                delimiter: ';',
+                handle_escapes: true,
+                escapes_only_in_quotes: false,
+                preserve_delimiters: false,
+                trim_results: false,
+            }
+        } else {
+            SplitConfig::semicolon_tokens()
+        };
         let mut tokens = Vec::new();
 
-        for token_str in parts {
+        for token_str in smart_split_borrowed(tokens_str, config) {
             let trimmed = token_str.trim();
             if trimmed.is_empty() {
                 continue;
             }
-            let token = Token::first(trimmed)?;
+            let offset = base + (trimmed.as_ptr() as usize - tokens_str.as_ptr() as usize);
+            let token = if canonical && trimmed.contains('\\') {
+                Self::parse_escaped_token(trimmed)
+            } else {
+                Token::first(trimmed)
+            }
+            .map_err(|e| e.offset_by(offset))?;
             tokens.push(token);
         }
 
         if tokens.is_empty() {
-            return Err("No valid tokens found".to_string());
+            return Err(MeteorError::parse(base, "No valid tokens found"));
         }
 
         Ok(tokens)
     }
 
+    /// Parse a single token whose key or value contains backslash escapes
+    ///
+    /// Error positions are relative to `s`.
+    fn parse_escaped_token(s: &str) -> Result<Token, MeteorError> {
+        let Some(&eq) = unescaped_positions(s, '=').first() else {
+            return Err(MeteorError::parse(
+                s.len(),
+                format!("Missing '=' separator in token: {}", s),
+            ));
+        };
+        let (raw_key, value) = (s[..eq].trim(), unescape_delimiters(&s[eq + 1..]));
+
+        let (namespace, key) = match unescaped_positions(raw_key, ':').first() {
+            Some(&colon) => (
+                Some(Namespace::from_string(&unescape_delimiters(
+                    &raw_key[..colon],
+                ))),
+                unescape_delimiters(&raw_key[colon + 1..]),
+            ),
+            None => (None, unescape_delimiters(raw_key)),
+        };
+
+        if key.is_empty() {
+            return Err(MeteorError::parse(
+                0,
+                format!("Token key cannot be empty: {}", s),
+            ));
+        }
+
+        Ok(match namespace {
+            Some(namespace) => Token::new_with_namespace(namespace, key, value),
+            None => Token::new(key, value),
+        })
+    }
+
     fn from_parts(
         context: Context,
         namespace: Namespace,
@@ -520,21 +707,30 @@ impl Meteor {
     }
 }
 
+/// Byte positions of `target` in `s` that are not preceded by a backslash escape
+fn unescaped_positions(s: &str, target: char) -> Vec<usize> {
+    let mut positions = Vec::new();
+    let mut escape_next = false;
+    for (pos, ch) in s.char_indices() {
+        if escape_next {
+            escape_next = false;
+        } else if ch == '\\' {
+            escape_next = true;
+        } else if ch == target {
+            positions.push(pos);
+        }
+    }
+    positions
+}
+
 impl fmt::Display for Meteor {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let tokens_str = self
-            .tokens
-            .iter()
-            .map(|token| format!("{}={}", token.key_notation(), token.value()))
-            .collect::<Vec<_>>()
-            .join(";");
-
         write!(
             f,
             "{}:{}:{}",
             self.context.to_string(),
             self.namespace.to_string(),
-            tokens_str
+            self.tokens_string()
         )
     }
 }
@@ -627,4 +823,154 @@ mod tests {
         let result = Meteor::parse("app:ui.widgets:message=\"Hello; World");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_meteor_validate_valid() {
+        let meteor = Meteor::new_with_tokens(
+            Context::app(),
+            Namespace::from_string("ui"),
+            vec![
+                Token::new("button", "click"),
+                Token::new_with_namespace(Namespace::from_string("ui"), "theme", "dark"),
+            ],
+        );
+        assert!(meteor.validate().is_ok());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_meteor_validate_unchecked_mismatch() {
+        let meteor = Meteor::force_create_unchecked(
+            Context::app(),
+            Namespace::from_string("ui"),
+            vec![
+                Token::new("button", "click"),
+                Token::new_with_namespace(Namespace::from_string("settings"), "theme", "dark"),
+            ],
+        );
+
+        match meteor.validate() {
+            Err(MeteorError::TokenNamespaceMismatch { .. }) => {}
+            other => panic!("expected namespace mismatch, got {:?}", other),
+        }
+
+        let empty = Meteor::force_create_unchecked(Context::app(), Namespace::root(), vec![]);
+        assert_eq!(empty.validate(), Err(MeteorError::EmptyTokens));
+    }
+
+    #[test]
+    fn test_meteor_tokens_string() {
+        let meteor = Meteor::new_with_tokens(
+            Context::user(),
+            Namespace::from_string("ui.widgets"),
+            vec![
+                Token::new("button", "submit"),
+                Token::new("list[0]", "first"),
+            ],
+        );
+
+        assert_eq!(meteor.tokens_string(), "button=submit;list[0]=first");
+        assert_eq!(
+            meteor.to_string(),
+            format!("user:ui.widgets:{}", meteor.tokens_string())
+        );
+    }
+
+    #[test]
+    fn test_meteor_canonical_string_round_trips_delimiters() {
+        let meteor = Meteor::new_with_tokens(
+            Context::app(),
+            Namespace::from_string("ui.widgets"),
+            vec![
+                Token::new("greeting", "hello; world"),
+                Token::new("formula", "x=y"),
+                Token::new("url", "http://host:80/path"),
+                Token::new("quote", "say \"hi\""),
+                Token::new("list[0]", "plain"),
+            ],
+        );
+
+        let rendered = meteor.to_canonical_string();
+        assert!(rendered.contains("greeting=hello\\; world"));
+        assert!(rendered.contains("formula=x\\=y"));
+
+        let reparsed = Meteor::parse_canonical(&rendered).unwrap();
+        assert_eq!(reparsed, meteor);
+        assert_eq!(reparsed.tokens()[1].value(), "x=y");
+    }
+
+    #[test]
+    fn test_meteor_parse_keeps_backslashes_literal() {
+        let meteor = Meteor::first(r#"app:ui:msg="hello \"world\"""#).unwrap();
+        assert_eq!(meteor.tokens()[0].value(), r#""hello \"world\"""#);
+
+        let meteor = Meteor::first(r"app:ui:path=a\\b").unwrap();
+        assert_eq!(meteor.tokens()[0].value(), r"a\\b");
+    }
+
+    #[test]
+    fn test_meteor_canonical_string_plain_matches_display() {
+        let meteor = Meteor::new(
+            Context::user(),
+            Namespace::from_string("settings"),
+            Token::new("theme", "dark"),
+        );
+        assert_eq!(meteor.to_canonical_string(), meteor.to_string());
+    }
+
+    #[test]
+    fn test_meteor_parse_reports_positions_in_whole_input() {
+        let err = Meteor::parse("app:ui:theme=dark;=oops").unwrap_err();
+        assert!(err.starts_with("Parse error at position 18:"), "{}", err);
+
+        let err = Meteor::parse("ui:theme=dark; broken").unwrap_err();
+        assert!(err.starts_with("Parse error at position 21:"), "{}", err);
+    }
+
+    #[test]
+    fn test_meteor_token_limit_boundary() {
+        let tokens = |n: usize| -> Vec<Token> {
+            (0..n).map(|i| Token::new(format!("k{}", i), "v")).collect()
+        };
+
+        let at_limit =
+            Meteor::try_new_with_token_limit(Context::app(), Namespace::root(), tokens(3), 3);
+        assert_eq!(at_limit.unwrap().tokens().len(), 3);
+
+        let past_limit =
+            Meteor::try_new_with_token_limit(Context::app(), Namespace::root(), tokens(4), 3);
+        assert_eq!(
+            past_limit,
+            Err(MeteorError::TooManyTokens { count: 4, limit: 3 })
+        );
+    }
+
+    #[test]
+    fn test_meteor_token_limit_is_opt_in() {
+        let limit = crate::config::MAX_TOKENS_PER_METEOR;
+        let tokens: Vec<Token> = (0..=limit)
+            .map(|i| Token::new(format!("k{}", i), "v"))
+            .collect();
+
+        assert!(
+            Meteor::try_new_with_tokens(Context::app(), Namespace::root(), tokens.clone()).is_ok()
+        );
+        let input = tokens
+            .iter()
+            .map(|t| format!("{}={}", t.key_notation(), t.value()))
+            .collect::<Vec<_>>()
+            .join(";");
+        assert!(Meteor::parse(&format!("app::{}", input)).is_ok());
+
+        let err =
+            Meteor::try_new_with_token_limit(Context::app(), Namespace::root(), tokens, limit)
+                .unwrap_err();
+        assert_eq!(
+            err,
+            MeteorError::TooManyTokens {
+                count: limit + 1,
+                limit
+            }
+        );
+    }
 }
@@ -9,11 +9,17 @@ mod storage_data;
 mod workspace;
 
 pub use engine::{
-    ControlCommand, Cursor, CursorGuard, EntriesIterator, MeteorEngine, MeteorsIterator,
-    NamespaceView,
+    ContextStats, ControlCommand, Cursor, CursorGuard, EngineSummary, EntriesIterator,
+    Inconsistency, LookupResult, MergeStrategy, MeteorEngine, MeteorsIterator, NamespaceChange,
+    NamespaceView, NormalizeMode, SetWarning,
+};
+pub use export::{
+    ChecksumAlgorithm, ContentType, DefaultChecksum, ExportData, ExportFormat, ExportMetadata,
+    ImportDiff, ImportResult,
 };
-pub use export::{ContentType, ExportData, ExportFormat, ExportMetadata, ImportDiff, ImportResult};
 pub use meteor::Meteor;
 pub use shower::{MeteorShower, METEOR_DELIMITER};
-pub use storage_data::StorageData;
+pub use storage_data::{ContextStorage, StorageData};
+#[cfg(feature = "workspace-instrumentation")]
+pub use workspace::MetricEvent;
 pub use workspace::ScratchSlotGuard;
@@ -1,7 +1,8 @@
 //! MeteorShower - collection container for fully-qualified Meteor tokens
 
+use super::export::{ExportData, ExportFormat};
 use super::meteor::Meteor;
-use crate::types::Context;
+use crate::types::{Context, Token};
 use std::collections::HashMap;
 
 /// Delimiter used to separate meteors in a MeteorShower string format
@@ -69,6 +70,34 @@ impl MeteorShower {
         self.meteors.push(meteor);
     }
 
+    /// Add a Meteor, folding it into an existing meteor of the same namespace
+    ///
+    /// If the shower already holds a meteor for the same (context, namespace),
+    /// the first such meteor absorbs the new tokens: existing keys take the
+    /// new value in place (last write wins) and new keys are appended.
+    /// Otherwise the meteor is added as with `add`.
+    pub fn merge(&mut self, meteor: Meteor) {
+        let existing = self
+            .namespace_index
+            .get(meteor.context().name())
+            .and_then(|namespaces| namespaces.get(&meteor.namespace().to_string()))
+            .and_then(|indices| indices.first().copied());
+
+        let Some(index) = existing else {
+            self.add(meteor);
+            return;
+        };
+
+        let target = &self.meteors[index];
+        let mut tokens = target.tokens().to_vec();
+        upsert_tokens(&mut tokens, meteor.tokens());
+        self.meteors[index] = Meteor::from_validated_tokens(
+            target.context().clone(),
+            target.namespace().clone(),
+            tokens,
+        );
+    }
+
     /// Get all meteors in the shower
     pub fn meteors(&self) -> &[Meteor] {
         &self.meteors
@@ -91,11 +120,19 @@ impl MeteorShower {
             .unwrap_or_default()
     }
 
-    /// Find a specific meteor by context, namespace, and key
+    /// Find the first meteor in (context, namespace) holding `key`
+    ///
+    /// Every token of each meteor is checked, not just the first, so keys
+    /// from multi-token meteors such as `app:ui:a=1;b=2` are found too.
     pub fn find(&self, context: &str, namespace: &str, key: &str) -> Option<&Meteor> {
         self.by_context_namespace(context, namespace)
             .into_iter()
-            .find(|meteor| meteor.token().key_notation() == key || meteor.token().key_str() == key)
+            .find(|meteor| {
+                meteor
+                    .tokens()
+                    .iter()
+                    .any(|token| token.key_notation() == key || token.key_str() == key)
+            })
     }
 
     /// Get all unique contexts in the shower
@@ -111,6 +148,66 @@ impl MeteorShower {
             .unwrap_or_default()
     }
 
+    /// Consolidate meteors sharing a (context, namespace) into one meteor each
+    ///
+    /// Read-only: the shower itself is not modified. Meteors are returned in
+    /// order of first appearance, tokens in shower order; when a key repeats,
+    /// the later value replaces the earlier one in place.
+    pub fn group_by_namespace(&self) -> Vec<Meteor> {
+        let mut order: Vec<(String, String)> = Vec::new();
+        let mut groups: HashMap<(String, String), (Meteor, Vec<Token>)> = HashMap::new();
+
+        for meteor in &self.meteors {
+            let key = (
+                meteor.context().name().to_string(),
+                meteor.namespace().to_string(),
+            );
+            let (_, tokens) = groups.entry(key.clone()).or_insert_with(|| {
+                order.push(key);
+                (meteor.clone(), Vec::new())
+            });
+
+            upsert_tokens(tokens, meteor.tokens());
+        }
+
+        order
+            .into_iter()
+            .filter_map(|key| groups.remove(&key))
+            .map(|(first, tokens)| {
+                Meteor::from_validated_tokens(
+                    first.context().clone(),
+                    first.namespace().clone(),
+                    tokens,
+                )
+            })
+            .collect()
+    }
+
+    /// Export the shower as one `ExportData` per (context, namespace)
+    ///
+    /// Meteors sharing a namespace are consolidated first, as in
+    /// `group_by_namespace`, so each export can be imported on its own.
+    /// Keys are written in notation form and each export carries the
+    /// default checksum, matching `MeteorEngine::export_namespace`.
+    pub fn export(&self, format: ExportFormat) -> Vec<ExportData> {
+        self.group_by_namespace()
+            .into_iter()
+            .map(|meteor| {
+                let tokens = meteor
+                    .tokens()
+                    .iter()
+                    .map(|token| (token.key_notation().to_string(), token.value().to_string()))
+                    .collect();
+                ExportData::new(
+                    meteor.context().name().to_string(),
+                    meteor.namespace().to_string(),
+                    tokens,
+                    format.clone(),
+                )
+            })
+            .collect()
+    }
+
     /// Count of meteors in the shower
     pub fn len(&self) -> usize {
         self.meteors.len()
@@ -128,6 +225,9 @@ impl MeteorShower {
     ///
     /// Each meteor can contain multiple tokens separated by single semicolons.
     /// Multiple consecutive semicolons (;;, ;;;) are syntax errors.
+    ///
+    /// Meteors keep their input order, and each meteor's `tokens()` keep the
+    /// order in which the tokens appeared in the stream.
     pub fn parse(input: &str) -> Result<Self, String> {
         let input = input.trim();
 
@@ -175,6 +275,19 @@ impl MeteorShower {
     }
 }
 
+/// Replace tokens whose key notation matches, append the rest in order
+fn upsert_tokens(tokens: &mut Vec<Token>, incoming: &[Token]) {
+    for token in incoming {
+        match tokens
+            .iter_mut()
+            .find(|existing| existing.key_notation() == token.key_notation())
+        {
+            Some(existing) => *existing = token.clone(),
+            None => tokens.push(token.clone()),
+        }
+    }
+}
+
 impl Default for MeteorShower {
     fn default() -> Self {
         MeteorShower::new()
@@ -236,4 +349,69 @@ mod tests {
         assert_eq!(app_meteors.len(), 1);
         assert_eq!(app_meteors[0].token().value(), "click");
     }
+
+    #[test]
+    fn test_group_by_namespace_consolidates_duplicates() {
+        let mut shower = MeteorShower::new();
+        shower.add(Meteor::new(
+            Context::app(),
+            Namespace::from_string("ui"),
+            Token::new("button", "click"),
+        ));
+        shower.add(Meteor::new(
+            Context::user(),
+            Namespace::from_string("settings"),
+            Token::new("theme", "dark"),
+        ));
+        shower.add(Meteor::new_with_tokens(
+            Context::app(),
+            Namespace::from_string("ui"),
+            vec![Token::new("theme", "light"), Token::new("button", "tap")],
+        ));
+
+        let grouped = shower.group_by_namespace();
+        assert_eq!(grouped.len(), 2);
+
+        let ui = &grouped[0];
+        assert_eq!(ui.context().name(), "app");
+        assert_eq!(ui.namespace().to_string(), "ui");
+        let tokens: Vec<(&str, &str)> = ui
+            .tokens()
+            .iter()
+            .map(|t| (t.key_notation(), t.value()))
+            .collect();
+        assert_eq!(tokens, vec![("button", "tap"), ("theme", "light")]);
+
+        assert_eq!(grouped[1].to_string(), "user:settings:theme=dark");
+
+        // Shower itself is untouched
+        assert_eq!(shower.len(), 3);
+    }
+
+    #[test]
+    fn test_parse_preserves_token_order() {
+        let shower = MeteorShower::parse("app:ui:a=1;b=2;c=3").unwrap();
+        let meteor = shower.find("app", "ui", "a").unwrap();
+
+        let keys: Vec<&str> = meteor.tokens().iter().map(|t| t.key_notation()).collect();
+        assert_eq!(keys, vec!["a", "b", "c"]);
+        let values: Vec<&str> = meteor.tokens().iter().map(|t| t.value()).collect();
+        assert_eq!(values, vec!["1", "2", "3"]);
+
+        // Later tokens of a multi-token meteor are findable as well
+        assert_eq!(shower.find("app", "ui", "c"), Some(meteor));
+        assert!(shower.find("app", "ui", "d").is_none());
+    }
+
+    #[test]
+    fn test_merge_folds_into_existing_namespace() {
+        let mut shower = MeteorShower::parse("app:ui:button=click;theme=dark").unwrap();
+        shower.merge(Meteor::first("app:ui:theme=light;lang=en").unwrap());
+        shower.merge(Meteor::first("user:main:name=ada").unwrap());
+
+        assert_eq!(shower.len(), 2);
+        let ui = shower.find("app", "ui", "button").unwrap();
+        assert_eq!(ui.to_string(), "app:ui:button=click;theme=light;lang=en");
+        assert!(shower.find("user", "main", "name").is_some());
+    }
 }
@@ -1,4 +1,16 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// Canonical key holding the default value of a directory path
+///
+/// The namespace root uses `index`; nested directories use `path.index`.
+pub(crate) fn default_key_for(path: &str) -> String {
+    if path.is_empty() {
+        "index".to_string()
+    } else {
+        format!("{}.index", path)
+    }
+}
 
 /// TreeNode represents either a directory (containing other nodes) or a file (pointing to data)
 #[derive(Debug, Clone)]
@@ -55,41 +67,139 @@ impl TreeNode {
     }
 }
 
+/// A stored value, owned by its key or shared by `intern_values`
+///
+/// Writes store an owned buffer, so an owned `String` is moved in without
+/// copying; only interning switches a value to a shared `Arc<str>`.
+#[derive(Debug, Clone)]
+enum StoredValue {
+    Owned(Box<str>),
+    Shared(Arc<str>),
+}
+
+impl std::ops::Deref for StoredValue {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        match self {
+            StoredValue::Owned(value) => value,
+            StoredValue::Shared(value) => value,
+        }
+    }
+}
+
 /// ContextStorage represents hybrid storage for a single context
 /// Combines flat canonical key storage with hierarchical tree indexing
 #[derive(Debug, Clone)]
 pub struct ContextStorage {
     /// Flat canonical key-value storage: "namespace:path.to.key" -> value
-    flat_data: HashMap<String, String>,
+    ///
+    /// Values are owned per key until `intern_values` makes equal values
+    /// share one allocation.
+    flat_data: HashMap<String, StoredValue>,
     /// Hierarchical navigation index: namespace -> TreeNode hierarchy
     tree_index: HashMap<String, TreeNode>,
+    /// Live key count per namespace, kept in step with flat_data for O(1) existence checks
+    namespace_key_counts: HashMap<String, usize>,
+    /// Whether `tree_index` is maintained (flat-only workloads can skip it)
+    tree_index_enabled: bool,
 }
 
 impl ContextStorage {
     /// Create new empty context storage
     pub fn new() -> Self {
+        Self::with_tree_index(true)
+    }
+
+    /// Create new empty context storage, optionally without a tree index
+    pub fn with_tree_index(enabled: bool) -> Self {
         Self {
             flat_data: HashMap::new(),
             tree_index: HashMap::new(),
+            namespace_key_counts: HashMap::new(),
+            tree_index_enabled: enabled,
+        }
+    }
+
+    /// Enable or disable the tree index
+    ///
+    /// Disabling drops the index; enabling rebuilds it from flat storage.
+    pub fn set_tree_index_enabled(&mut self, enabled: bool) {
+        if enabled == self.tree_index_enabled {
+            return;
+        }
+        self.tree_index_enabled = enabled;
+        self.tree_index = HashMap::new();
+        if enabled {
+            let canonical_keys: Vec<String> = self.flat_data.keys().cloned().collect();
+            for canonical_key in canonical_keys {
+                if let Some((namespace, key)) = canonical_key.split_once(':') {
+                    self.update_tree_index(namespace, key, &canonical_key);
+                }
+            }
         }
     }
 
     /// Set value using canonical key, updating both flat storage and tree index
     pub fn set(&mut self, namespace: &str, key: &str, value: &str) {
+        self.insert_value(namespace, key, StoredValue::Owned(Box::from(value)));
+    }
+
+    /// Like `set`, but moves an owned value into storage
+    pub fn set_owned(&mut self, namespace: &str, key: &str, value: String) {
+        self.insert_value(namespace, key, StoredValue::Owned(value.into_boxed_str()));
+    }
+
+    fn insert_value(&mut self, namespace: &str, key: &str, value: StoredValue) {
         let canonical_key = format!("{}:{}", namespace, key);
 
         // Store in flat data
-        self.flat_data
-            .insert(canonical_key.clone(), value.to_string());
+        if self
+            .flat_data
+            .insert(canonical_key.clone(), value)
+            .is_none()
+        {
+            *self
+                .namespace_key_counts
+                .entry(namespace.to_string())
+                .or_insert(0) += 1;
+        }
 
         // Update tree index
-        self.update_tree_index(namespace, key, &canonical_key);
+        if self.tree_index_enabled {
+            self.update_tree_index(namespace, key, &canonical_key);
+        }
     }
 
     /// Get value by canonical key (O(1) access)
     pub fn get(&self, namespace: &str, key: &str) -> Option<&str> {
         let canonical_key = format!("{}:{}", namespace, key);
-        self.flat_data.get(&canonical_key).map(|s| s.as_str())
+        self.flat_data.get(&canonical_key).map(|s| &**s)
+    }
+
+    /// Share one allocation between equal values, using `pool` as the intern table
+    ///
+    /// Returns how many values were replaced by an existing shared copy.
+    fn intern_values(&mut self, pool: &mut HashSet<Arc<str>>) -> usize {
+        let mut replaced = 0;
+        for value in self.flat_data.values_mut() {
+            match (pool.get(&**value), &*value) {
+                (Some(shared), StoredValue::Shared(current)) if Arc::ptr_eq(shared, current) => {}
+                (Some(shared), _) => {
+                    *value = StoredValue::Shared(Arc::clone(shared));
+                    replaced += 1;
+                }
+                (None, StoredValue::Shared(current)) => {
+                    pool.insert(Arc::clone(current));
+                }
+                (None, StoredValue::Owned(current)) => {
+                    let shared: Arc<str> = Arc::from(&**current);
+                    pool.insert(Arc::clone(&shared));
+                    *value = StoredValue::Shared(shared);
+                }
+            }
+        }
+        replaced
     }
 
     /// Check if path exists as a file
@@ -109,21 +219,13 @@ impl ContextStorage {
 
     /// Check if directory has a default value (.index pattern)
     pub fn has_default(&self, namespace: &str, path: &str) -> bool {
-        let index_key = if path.is_empty() {
-            "index".to_string()
-        } else {
-            format!("{}.index", path)
-        };
+        let index_key = default_key_for(path);
         self.is_file(namespace, &index_key)
     }
 
     /// Get default value for a directory path
     pub fn get_default(&self, namespace: &str, path: &str) -> Option<&str> {
-        let index_key = if path.is_empty() {
-            "index".to_string()
-        } else {
-            format!("{}.index", path)
-        };
+        let index_key = default_key_for(path);
         self.get(namespace, &index_key)
     }
 
@@ -147,9 +249,25 @@ impl ContextStorage {
         results
     }
 
+    /// Check whether a namespace holds any keys (O(1), no allocation)
+    pub fn has_keys_in(&self, namespace: &str) -> bool {
+        self.namespace_key_counts.contains_key(namespace)
+    }
+
+    /// Check whether this context holds any keys
+    pub fn has_keys(&self) -> bool {
+        !self.flat_data.is_empty()
+    }
+
     /// Get all namespaces in this context
+    ///
+    /// Without a tree index only namespaces currently holding keys are listed.
     pub fn namespaces(&self) -> Vec<String> {
-        let mut namespaces: Vec<String> = self.tree_index.keys().cloned().collect();
+        let mut namespaces: Vec<String> = if self.tree_index_enabled {
+            self.tree_index.keys().cloned().collect()
+        } else {
+            self.namespace_key_counts.keys().cloned().collect()
+        };
         namespaces.sort();
         namespaces
     }
@@ -158,6 +276,7 @@ impl ContextStorage {
     pub fn delete_key(&mut self, namespace: &str, key: &str) -> bool {
         let canonical_key = format!("{}:{}", namespace, key);
         if self.flat_data.remove(&canonical_key).is_some() {
+            self.release_key_count(namespace);
             // Also remove from tree index
             self.remove_from_tree_index(namespace, key);
             true
@@ -168,11 +287,13 @@ impl ContextStorage {
 
     /// Delete entire namespace
     pub fn delete_namespace(&mut self, namespace: &str) -> bool {
-        let had_namespace = self.tree_index.contains_key(namespace);
+        let had_namespace = self.tree_index.contains_key(namespace)
+            || self.namespace_key_counts.contains_key(namespace);
 
         // Remove all flat data for this namespace
         let prefix = format!("{}:", namespace);
         self.flat_data.retain(|key, _| !key.starts_with(&prefix));
+        self.namespace_key_counts.remove(namespace);
 
         // Remove tree index for namespace
         self.tree_index.remove(namespace);
@@ -180,6 +301,42 @@ impl ContextStorage {
         had_namespace
     }
 
+    /// Internal: Drop one key from a namespace's live count
+    fn release_key_count(&mut self, namespace: &str) {
+        if let Some(count) = self.namespace_key_counts.get_mut(namespace) {
+            *count -= 1;
+            if *count == 0 {
+                self.namespace_key_counts.remove(namespace);
+            }
+        }
+    }
+
+    /// Canonical keys referenced by tree index files that have no flat entry
+    pub(crate) fn dangling_tree_leaves(&self) -> Vec<String> {
+        let mut leaves = Vec::new();
+        for ns_tree in self.tree_index.values() {
+            Self::collect_file_leaves(ns_tree, &mut leaves);
+        }
+
+        let mut dangling: Vec<String> = leaves
+            .into_iter()
+            .filter(|canonical_key| !self.flat_data.contains_key(canonical_key))
+            .collect();
+        dangling.sort();
+        dangling
+    }
+
+    /// Internal: Collect canonical keys of every file node under a tree node
+    fn collect_file_leaves(node: &TreeNode, leaves: &mut Vec<String>) {
+        if let Some(canonical_key) = node.canonical_key() {
+            leaves.push(canonical_key.to_string());
+        } else if let Some(children) = node.children() {
+            for child in children.values() {
+                Self::collect_file_leaves(child, leaves);
+            }
+        }
+    }
+
     /// Internal: Update tree index when setting a value
     fn update_tree_index(&mut self, namespace: &str, key: &str, canonical_key: &str) {
         // Ensure namespace exists in tree
@@ -356,6 +513,8 @@ impl Default for ContextStorage {
 pub struct StorageData {
     /// Context-isolated hybrid storage systems
     contexts: HashMap<String, ContextStorage>,
+    /// Whether contexts maintain a tree index for directory queries
+    tree_index_enabled: bool,
 }
 
 #[cfg(test)]
@@ -373,15 +532,44 @@ mod tests {
         assert!(!dir.is_file());
         assert!(dir.canonical_key().is_none());
     }
+
+    #[test]
+    fn storage_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<StorageData>();
+        assert_send_sync::<ContextStorage>();
+    }
 }
 
 impl StorageData {
     pub fn new() -> Self {
         Self {
             contexts: HashMap::new(),
+            tree_index_enabled: true,
         }
     }
 
+    /// Whether directory queries are backed by a tree index
+    pub fn tree_index_enabled(&self) -> bool {
+        self.tree_index_enabled
+    }
+
+    /// Enable or disable the tree index for all current and future contexts
+    ///
+    /// With the index disabled, flat get/set/find keep working while
+    /// key-level `is_directory` reports `false`.
+    pub fn set_tree_index_enabled(&mut self, enabled: bool) {
+        self.tree_index_enabled = enabled;
+        for context in self.contexts.values_mut() {
+            context.set_tree_index_enabled(enabled);
+        }
+    }
+
+    /// Get the storage of a single context
+    pub fn context(&self, context: &str) -> Option<&ContextStorage> {
+        self.contexts.get(context)
+    }
+
     /// Get a value by context, namespace, and key (O(1) access)
     pub fn get(&self, context: &str, namespace: &str, key: &str) -> Option<&str> {
         self.contexts.get(context)?.get(namespace, key)
@@ -389,12 +577,22 @@ impl StorageData {
 
     /// Set a value by context, namespace, and key
     pub fn set(&mut self, context: &str, namespace: &str, key: &str, value: &str) {
+        let tree_index_enabled = self.tree_index_enabled;
         self.contexts
             .entry(context.to_string())
-            .or_insert_with(ContextStorage::new)
+            .or_insert_with(|| ContextStorage::with_tree_index(tree_index_enabled))
             .set(namespace, key, value);
     }
 
+    /// Set an owned value by context, namespace, and key, moving it into storage
+    pub fn set_owned(&mut self, context: &str, namespace: &str, key: &str, value: String) {
+        let tree_index_enabled = self.tree_index_enabled;
+        self.contexts
+            .entry(context.to_string())
+            .or_insert_with(|| ContextStorage::with_tree_index(tree_index_enabled))
+            .set_owned(namespace, key, value);
+    }
+
     /// Check if path exists as a file
     pub fn is_file(&self, context: &str, namespace: &str, key: &str) -> bool {
         self.contexts
@@ -409,13 +607,26 @@ impl StorageData {
             .map_or(false, |ctx| ctx.is_directory(namespace, path))
     }
 
-    /// Check if namespace exists in context
+    /// Check if namespace exists in context (holds at least one key)
     pub fn namespace_exists(&self, context: &str, namespace: &str) -> bool {
-        self.contexts.get(context).map_or(false, |ctx| {
-            ctx.flat_data
-                .keys()
-                .any(|key| key.starts_with(&format!("{}:", namespace)))
-        })
+        self.namespace_has_keys(context, namespace)
+    }
+
+    /// Check if a context holds at least one key (O(1))
+    pub fn context_has_keys(&self, context: &str) -> bool {
+        self.contexts.get(context).is_some_and(|ctx| ctx.has_keys())
+    }
+
+    /// Check if a namespace holds any keys, without allocating
+    pub fn namespace_has_keys(&self, context: &str, namespace: &str) -> bool {
+        self.contexts
+            .get(context)
+            .is_some_and(|ctx| ctx.has_keys_in(namespace))
+    }
+
+    /// Check if no context holds any keys
+    pub fn is_empty(&self) -> bool {
+        self.contexts.values().all(|ctx| ctx.flat_data.is_empty())
     }
 
     /// Check if directory has default value
@@ -456,6 +667,19 @@ impl StorageData {
         self.contexts.remove(context).is_some()
     }
 
+    /// Deduplicate equal values across all contexts so they share storage
+    ///
+    /// Reads are unaffected; only memory use changes. Values written later
+    /// get their own allocation until the next call. Returns how many values
+    /// now point at a shared copy instead of their own.
+    pub fn intern_values(&mut self) -> usize {
+        let mut pool = HashSet::new();
+        self.contexts
+            .values_mut()
+            .map(|ctx| ctx.intern_values(&mut pool))
+            .sum()
+    }
+
     /// Get all contexts
     pub fn contexts(&self) -> Vec<String> {
         let mut contexts: Vec<String> = self.contexts.keys().cloned().collect();
@@ -470,6 +694,33 @@ impl StorageData {
             .map_or(Vec::new(), |ctx| ctx.namespaces())
     }
 
+    /// Canonical keys in a context's tree index that no longer resolve to flat entries
+    pub(crate) fn dangling_tree_leaves(&self, context: &str) -> Vec<String> {
+        self.contexts
+            .get(context)
+            .map_or(Vec::new(), |ctx| ctx.dangling_tree_leaves())
+    }
+
+    /// Test-only: drop a flat entry while leaving the tree index untouched
+    #[cfg(test)]
+    pub(crate) fn remove_flat_entry_unchecked(
+        &mut self,
+        context: &str,
+        namespace: &str,
+        key: &str,
+    ) -> bool {
+        self.contexts.get_mut(context).is_some_and(|ctx| {
+            let removed = ctx
+                .flat_data
+                .remove(&format!("{}:{}", namespace, key))
+                .is_some();
+            if removed {
+                ctx.release_key_count(namespace);
+            }
+            removed
+        })
+    }
+
     /// Convert to JSON string (for serialization)
     pub fn to_json(&self) -> String {
         // Simple JSON serialization - in real implementation would use serde
@@ -1,23 +1,133 @@
-#[cfg(feature = "workspace-instrumentation")]
-use std::cell::Cell;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 type ContextNamespaceKey = (String, String);
 
+/// Default maximum number of cached query patterns per namespace
+pub(crate) const DEFAULT_QUERY_CACHE_CAPACITY: usize = 128;
+
+/// Size-bounded LRU cache of pattern → matching keys for one namespace
+///
+/// Uses interior mutability so read-only queries (e.g. `NamespaceView::find_keys`)
+/// can populate it; a mutex and an atomic tick keep the engine `Sync`. Each
+/// access stamps the entry with a monotonically increasing tick; inserting at
+/// capacity evicts the entry with the oldest tick.
+#[derive(Debug)]
+pub(crate) struct QueryCache {
+    capacity: usize,
+    entries: Mutex<HashMap<String, (u64, Vec<String>)>>,
+    tick: AtomicU64,
+}
+
+impl Clone for QueryCache {
+    fn clone(&self) -> Self {
+        Self {
+            capacity: self.capacity,
+            entries: Mutex::new(self.entries().clone()),
+            tick: AtomicU64::new(self.tick.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+impl QueryCache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(HashMap::new()),
+            tick: AtomicU64::new(0),
+        }
+    }
+
+    /// Lock the entries; a poisoned cache still holds valid (if stale) results
+    fn entries(&self) -> MutexGuard<'_, HashMap<String, (u64, Vec<String>)>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn next_tick(&self) -> u64 {
+        self.tick.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    /// Look up a pattern, marking it as most recently used
+    pub(crate) fn get(&self, pattern: &str) -> Option<Vec<String>> {
+        let tick = self.next_tick();
+        let mut entries = self.entries();
+        let (last_used, keys) = entries.get_mut(pattern)?;
+        *last_used = tick;
+        Some(keys.clone())
+    }
+
+    /// Cache a pattern's results, evicting the least recently used on overflow
+    pub(crate) fn insert(&self, pattern: String, keys: Vec<String>) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let tick = self.next_tick();
+        let mut entries = self.entries();
+        if !entries.contains_key(&pattern) && entries.len() >= self.capacity {
+            if let Some(oldest) = entries
+                .iter()
+                .min_by_key(|(_, (last_used, _))| *last_used)
+                .map(|(pattern, _)| pattern.clone())
+            {
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(pattern, (tick, keys));
+    }
+
+    #[cfg(test)]
+    pub(crate) fn contains(&self, pattern: &str) -> bool {
+        self.entries().contains_key(pattern)
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.entries().len()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Change the capacity, evicting least recently used entries to fit
+    pub(crate) fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        let entries = self
+            .entries
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner);
+        while entries.len() > capacity {
+            if let Some(oldest) = entries
+                .iter()
+                .min_by_key(|(_, (last_used, _))| *last_used)
+                .map(|(pattern, _)| pattern.clone())
+            {
+                entries.remove(&oldest);
+            }
+        }
+    }
+
+    pub(crate) fn clear(&self) {
+        self.entries().clear();
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct NamespaceWorkspace {
     pub(crate) key_order: Vec<String>,
-    pub(crate) query_cache: HashMap<String, Vec<String>>,
+    pub(crate) query_cache: QueryCache,
     pub(crate) last_modified: u64,
     #[cfg(feature = "workspace-instrumentation")]
-    pub(crate) cache_hits: Cell<u64>,
+    pub(crate) cache_hits: AtomicU64,
     #[cfg(feature = "workspace-instrumentation")]
-    pub(crate) cache_misses: Cell<u64>,
+    pub(crate) cache_misses: AtomicU64,
     #[cfg(feature = "workspace-instrumentation")]
-    pub(crate) iteration_count: Cell<u64>,
+    pub(crate) iteration_count: AtomicU64,
     #[cfg(feature = "workspace-instrumentation")]
-    pub(crate) keys_iterated: Cell<u64>,
+    pub(crate) keys_iterated: AtomicU64,
 }
 
 impl Clone for NamespaceWorkspace {
@@ -27,31 +137,31 @@ impl Clone for NamespaceWorkspace {
             query_cache: self.query_cache.clone(),
             last_modified: self.last_modified,
             #[cfg(feature = "workspace-instrumentation")]
-            cache_hits: Cell::new(self.cache_hits.get()),
+            cache_hits: AtomicU64::new(self.cache_hits.load(Ordering::Relaxed)),
             #[cfg(feature = "workspace-instrumentation")]
-            cache_misses: Cell::new(self.cache_misses.get()),
+            cache_misses: AtomicU64::new(self.cache_misses.load(Ordering::Relaxed)),
             #[cfg(feature = "workspace-instrumentation")]
-            iteration_count: Cell::new(self.iteration_count.get()),
+            iteration_count: AtomicU64::new(self.iteration_count.load(Ordering::Relaxed)),
             #[cfg(feature = "workspace-instrumentation")]
-            keys_iterated: Cell::new(self.keys_iterated.get()),
+            keys_iterated: AtomicU64::new(self.keys_iterated.load(Ordering::Relaxed)),
         }
     }
 }
 
 impl NamespaceWorkspace {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn with_cache_capacity(capacity: usize) -> Self {
         Self {
             key_order: Vec::new(),
-            query_cache: HashMap::new(),
+            query_cache: QueryCache::new(capacity),
             last_modified: current_timestamp(),
             #[cfg(feature = "workspace-instrumentation")]
-            cache_hits: Cell::new(0),
+            cache_hits: AtomicU64::new(0),
             #[cfg(feature = "workspace-instrumentation")]
-            cache_misses: Cell::new(0),
+            cache_misses: AtomicU64::new(0),
             #[cfg(feature = "workspace-instrumentation")]
-            iteration_count: Cell::new(0),
+            iteration_count: AtomicU64::new(0),
             #[cfg(feature = "workspace-instrumentation")]
-            keys_iterated: Cell::new(0),
+            keys_iterated: AtomicU64::new(0),
         }
     }
 
@@ -65,8 +175,8 @@ impl NamespaceWorkspace {
         #[cfg(feature = "workspace-instrumentation")]
         {
             // Reset cache metrics (tied to cache validity)
-            self.cache_hits.set(0);
-            self.cache_misses.set(0);
+            self.cache_hits.store(0, Ordering::Relaxed);
+            self.cache_misses.store(0, Ordering::Relaxed);
             // Iteration metrics persist (lifetime statistics)
         }
     }
@@ -78,6 +188,14 @@ impl NamespaceWorkspace {
         self.touch();
     }
 
+    /// Place `key` at `index` in the order (clamped), moving it if present
+    pub(crate) fn insert_key_at(&mut self, key: &str, index: usize) {
+        self.key_order.retain(|k| k != key);
+        let index = index.min(self.key_order.len());
+        self.key_order.insert(index, key.to_string());
+        self.touch();
+    }
+
     pub(crate) fn remove_key(&mut self, key: &str) {
         self.key_order.retain(|k| k != key);
         self.touch();
@@ -85,38 +203,39 @@ impl NamespaceWorkspace {
 
     #[cfg(feature = "workspace-instrumentation")]
     pub(crate) fn record_cache_hit(&self) {
-        self.cache_hits.set(self.cache_hits.get() + 1);
+        self.cache_hits.fetch_add(1, Ordering::Relaxed);
     }
 
     #[cfg(feature = "workspace-instrumentation")]
     pub(crate) fn record_cache_miss(&self) {
-        self.cache_misses.set(self.cache_misses.get() + 1);
+        self.cache_misses.fetch_add(1, Ordering::Relaxed);
     }
 
     #[cfg(feature = "workspace-instrumentation")]
     pub(crate) fn cache_hit_ratio(&self) -> f64 {
-        let total = self.cache_hits.get() + self.cache_misses.get();
+        let hits = self.cache_hits.load(Ordering::Relaxed);
+        let total = hits + self.cache_misses.load(Ordering::Relaxed);
         if total == 0 {
             0.0
         } else {
-            self.cache_hits.get() as f64 / total as f64
+            hits as f64 / total as f64
         }
     }
 
     #[cfg(feature = "workspace-instrumentation")]
     pub(crate) fn record_iteration(&self, key_count: usize) {
-        self.iteration_count.set(self.iteration_count.get() + 1);
+        self.iteration_count.fetch_add(1, Ordering::Relaxed);
         self.keys_iterated
-            .set(self.keys_iterated.get() + key_count as u64);
+            .fetch_add(key_count as u64, Ordering::Relaxed);
     }
 
     #[cfg(feature = "workspace-instrumentation")]
     pub(crate) fn avg_keys_per_iteration(&self) -> f64 {
-        let iters = self.iteration_count.get();
+        let iters = self.iteration_count.load(Ordering::Relaxed);
         if iters == 0 {
             0.0
         } else {
-            self.keys_iterated.get() as f64 / iters as f64
+            self.keys_iterated.load(Ordering::Relaxed) as f64 / iters as f64
         }
     }
 }
@@ -273,10 +392,63 @@ impl<'a> Drop for ScratchSlotGuard<'a> {
     }
 }
 
+/// Instrumentation event delivered to a metrics sink as it happens
+#[cfg(feature = "workspace-instrumentation")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MetricEvent {
+    /// A namespace query was answered from the query cache
+    CacheHit {
+        context: String,
+        namespace: String,
+        pattern: String,
+    },
+    /// A namespace query had to go to storage
+    CacheMiss {
+        context: String,
+        namespace: String,
+        pattern: String,
+    },
+    /// A namespace was iterated, visiting `keys` keys
+    Iteration {
+        context: String,
+        namespace: String,
+        keys: usize,
+    },
+}
+
+/// Callback receiving `MetricEvent`s; the mutex lets read-only engine paths
+/// emit events while keeping the engine `Send + Sync`
+#[cfg(feature = "workspace-instrumentation")]
+pub(crate) struct MetricsSink(std::sync::Mutex<Box<dyn FnMut(MetricEvent) + Send>>);
+
+#[cfg(feature = "workspace-instrumentation")]
+impl MetricsSink {
+    pub(crate) fn new(sink: impl FnMut(MetricEvent) + Send + 'static) -> Self {
+        Self(std::sync::Mutex::new(Box::new(sink)))
+    }
+
+    pub(crate) fn emit(&self, event: MetricEvent) {
+        // A sink that panicked once still receives later events
+        let mut sink = self
+            .0
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        (*sink)(event);
+    }
+}
+
+#[cfg(feature = "workspace-instrumentation")]
+impl std::fmt::Debug for MetricsSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("MetricsSink(..)")
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct EngineWorkspace {
     namespaces: HashMap<ContextNamespaceKey, NamespaceWorkspace>,
     scratch_slots: HashMap<String, ScratchSlot>,
+    query_cache_capacity: usize,
 }
 
 impl EngineWorkspace {
@@ -284,6 +456,19 @@ impl EngineWorkspace {
         Self {
             namespaces: HashMap::new(),
             scratch_slots: HashMap::new(),
+            query_cache_capacity: DEFAULT_QUERY_CACHE_CAPACITY,
+        }
+    }
+
+    pub(crate) fn query_cache_capacity(&self) -> usize {
+        self.query_cache_capacity
+    }
+
+    /// Set the per-namespace query cache bound, shrinking existing caches
+    pub(crate) fn set_query_cache_capacity(&mut self, capacity: usize) {
+        self.query_cache_capacity = capacity;
+        for workspace in self.namespaces.values_mut() {
+            workspace.query_cache.set_capacity(capacity);
         }
     }
 
@@ -293,9 +478,10 @@ impl EngineWorkspace {
         namespace: &str,
     ) -> &mut NamespaceWorkspace {
         let key = (context.to_string(), namespace.to_string());
+        let capacity = self.query_cache_capacity;
         self.namespaces
             .entry(key)
-            .or_insert_with(NamespaceWorkspace::new)
+            .or_insert_with(|| NamespaceWorkspace::with_cache_capacity(capacity))
     }
 
     pub(crate) fn get_namespace(
@@ -307,6 +493,12 @@ impl EngineWorkspace {
         self.namespaces.get(&key)
     }
 
+    pub(crate) fn namespaces(&self) -> impl Iterator<Item = (&str, &str, &NamespaceWorkspace)> {
+        self.namespaces
+            .iter()
+            .map(|((ctx, ns), workspace)| (ctx.as_str(), ns.as_str(), workspace))
+    }
+
     #[allow(dead_code)]
     pub(crate) fn invalidate_namespace(&mut self, context: &str, namespace: &str) {
         let key = (context.to_string(), namespace.to_string());
@@ -324,7 +516,6 @@ impl EngineWorkspace {
         }
     }
 
-    #[allow(dead_code)]
     pub(crate) fn invalidate_all(&mut self) {
         for workspace in self.namespaces.values_mut() {
             workspace.invalidate_caches();
@@ -379,11 +570,15 @@ impl EngineWorkspace {
     pub(crate) fn workspace_status(&self) -> WorkspaceStatus {
         #[cfg(feature = "workspace-instrumentation")]
         let (total_hits, total_misses, hit_ratio) = {
-            let hits: u64 = self.namespaces.values().map(|ns| ns.cache_hits.get()).sum();
+            let hits: u64 = self
+                .namespaces
+                .values()
+                .map(|ns| ns.cache_hits.load(Ordering::Relaxed))
+                .sum();
             let misses: u64 = self
                 .namespaces
                 .values()
-                .map(|ns| ns.cache_misses.get())
+                .map(|ns| ns.cache_misses.load(Ordering::Relaxed))
                 .sum();
             let total = hits + misses;
             let ratio = if total == 0 {
@@ -399,12 +594,12 @@ impl EngineWorkspace {
             let iters: u64 = self
                 .namespaces
                 .values()
-                .map(|ns| ns.iteration_count.get())
+                .map(|ns| ns.iteration_count.load(Ordering::Relaxed))
                 .sum();
             let keys: u64 = self
                 .namespaces
                 .values()
-                .map(|ns| ns.keys_iterated.get())
+                .map(|ns| ns.keys_iterated.load(Ordering::Relaxed))
                 .sum();
             let avg = if iters == 0 {
                 0.0
@@ -683,4 +878,59 @@ mod tests {
         assert_eq!(status.total_cached_queries, 1);
         assert_eq!(status.total_ordered_keys, 1);
     }
+
+    #[test]
+    fn test_query_cache_lru_bounded() {
+        let cache = QueryCache::new(4);
+        for i in 0..50 {
+            cache.insert(format!("pattern{}", i), vec![format!("key{}", i)]);
+            assert!(cache.len() <= 4);
+        }
+        assert_eq!(cache.len(), 4);
+        assert!(cache.contains("pattern49"));
+        assert!(!cache.contains("pattern0"));
+    }
+
+    #[test]
+    fn test_query_cache_recently_used_survives_eviction() {
+        let cache = QueryCache::new(3);
+        cache.insert("a".to_string(), vec!["1".to_string()]);
+        cache.insert("b".to_string(), vec![]);
+        cache.insert("c".to_string(), vec![]);
+
+        // Touch "a" so "b" becomes least recently used
+        assert_eq!(cache.get("a"), Some(vec!["1".to_string()]));
+        cache.insert("d".to_string(), vec![]);
+
+        assert!(cache.contains("a"));
+        assert!(!cache.contains("b"));
+        assert!(cache.contains("c"));
+        assert!(cache.contains("d"));
+    }
+
+    #[test]
+    fn test_query_cache_capacity_configuration() {
+        let mut workspace = EngineWorkspace::new();
+        assert_eq!(
+            workspace
+                .get_or_create_namespace("app", "main")
+                .query_cache
+                .capacity(),
+            DEFAULT_QUERY_CACHE_CAPACITY
+        );
+
+        let ns = workspace.get_or_create_namespace("app", "main");
+        for i in 0..10 {
+            ns.query_cache.insert(format!("q{}", i), vec![]);
+        }
+
+        workspace.set_query_cache_capacity(2);
+        let ns = workspace.get_namespace("app", "main").unwrap();
+        assert_eq!(ns.query_cache.len(), 2);
+        assert!(ns.query_cache.contains("q8"));
+        assert!(ns.query_cache.contains("q9"));
+
+        let fresh = workspace.get_or_create_namespace("app", "ui");
+        assert_eq!(fresh.query_cache.capacity(), 2);
+    }
 }
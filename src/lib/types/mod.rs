@@ -20,10 +20,14 @@ pub use key::{
     extract_base_name, has_brackets, reverse_transform_key, transform_key, BracketNotation,
     TokenKey,
 };
+#[cfg(feature = "workspace-instrumentation")]
+pub use meteor::MetricEvent;
 pub use meteor::{
-    ContentType, ControlCommand, Cursor, CursorGuard, EntriesIterator, ExportData, ExportFormat,
-    ExportMetadata, ImportDiff, ImportResult, Meteor, MeteorEngine, MeteorShower, MeteorsIterator,
-    NamespaceView, ScratchSlotGuard, StorageData, METEOR_DELIMITER,
+    ChecksumAlgorithm, ContentType, ContextStats, ContextStorage, ControlCommand, Cursor,
+    CursorGuard, DefaultChecksum, EngineSummary, EntriesIterator, ExportData, ExportFormat,
+    ExportMetadata, ImportDiff, ImportResult, Inconsistency, LookupResult, MergeStrategy, Meteor,
+    MeteorEngine, MeteorShower, MeteorsIterator, NamespaceChange, NamespaceView, NormalizeMode,
+    ScratchSlotGuard, SetWarning, StorageData, METEOR_DELIMITER,
 };
 pub use namespace::{
     Namespace, MAX_NAMESPACE_PART_LENGTH, NAMESPACE_ERROR_DEPTH, NAMESPACE_WARNING_DEPTH,
@@ -82,6 +82,14 @@ impl Namespace {
         Ok(Namespace { parts })
     }
 
+    /// Check if this is the root (empty) namespace
+    ///
+    /// Root is distinct from the engine's default `main` namespace: it renders
+    /// as `""` and has depth 0.
+    pub fn is_root(&self) -> bool {
+        self.parts.is_empty()
+    }
+
     /// Get the namespace depth
     pub fn depth(&self) -> usize {
         self.parts.len()
@@ -103,10 +111,28 @@ impl Namespace {
     }
 
     /// Convert to dot-separated string
+    ///
+    /// The root namespace renders as `""`, and `from_string()` of the result
+    /// yields an equal namespace for root, single and multi-segment names.
     pub fn to_string(&self) -> String {
         self.parts.join(".")
     }
 
+    /// Append a dot-separated suffix, returning the child namespace
+    ///
+    /// Empty segments are dropped, so joining onto root yields just the
+    /// suffix (`ui`, not `.ui`) and stray dots in `suffix` are normalized.
+    pub fn join(&self, suffix: &str) -> Namespace {
+        let mut parts = self.parts.clone();
+        parts.extend(
+            suffix
+                .split('.')
+                .filter(|part| !part.is_empty())
+                .map(|part| part.to_string()),
+        );
+        Namespace { parts }
+    }
+
     /// Check if this namespace is a parent of another
     pub fn is_parent_of(&self, other: &Namespace) -> bool {
         if self.parts.len() >= other.parts.len() {
@@ -127,6 +153,7 @@ impl FromStr for Namespace {
     }
 }
 
+/// Renders the same dot-separated form as `to_string()`; root is `""`
 impl fmt::Display for Namespace {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.to_string())
@@ -325,4 +352,51 @@ mod tests {
         assert!(Namespace::from_str("global").is_err()); // global is reserved
         assert!(Namespace::from_str("ui..widgets").is_err());
     }
+
+    #[test]
+    fn test_namespace_join() {
+        // Joining onto root does not produce a leading dot
+        let ui = Namespace::root().join("ui");
+        assert_eq!(ui.to_string(), "ui");
+        assert_eq!(ui.depth(), 1);
+
+        // Joining onto a deep namespace appends a single segment
+        let deep = Namespace::from_string("ui.widgets").join("buttons");
+        assert_eq!(deep.to_string(), "ui.widgets.buttons");
+        assert!(Namespace::from_string("ui.widgets").is_parent_of(&deep));
+
+        // Dotted suffixes are split into parts and stray dots normalized
+        let dotted = Namespace::from_string("app").join(".db..config.");
+        assert_eq!(dotted.parts(), &["app", "db", "config"]);
+        assert_eq!(dotted, Namespace::from_string("app.db.config"));
+
+        // Empty suffix leaves the namespace unchanged
+        assert_eq!(
+            Namespace::from_string("ui").join(""),
+            Namespace::from_string("ui")
+        );
+    }
+
+    #[test]
+    fn test_root_round_trip() {
+        let root = Namespace::root();
+        assert!(root.is_root());
+        assert_eq!(root.depth(), 0);
+        assert_eq!(format!("{}", root), "");
+        assert_eq!(Namespace::from_string(&root.to_string()), root);
+        assert_eq!(Namespace::try_from_string("").unwrap(), root);
+    }
+
+    #[test]
+    fn test_segment_round_trip() {
+        for name in ["ui", "ui.widgets", "db.config.pool"] {
+            let ns = Namespace::from_string(name);
+            assert!(!ns.is_root());
+            assert_eq!(format!("{}", ns), name);
+            assert_eq!(Namespace::from_string(&ns.to_string()), ns);
+        }
+
+        // The engine's default namespace is a named segment, not root
+        assert!(!Namespace::from_string("main").is_root());
+    }
 }
@@ -1,6 +1,6 @@
 //! Token type - individual key identifiers with value
 
-use crate::types::{Namespace, TokenKey};
+use crate::types::{MeteorError, Namespace, TokenKey};
 use std::fmt;
 use std::str::FromStr;
 
@@ -76,9 +76,16 @@ impl Token {
     }
 
     /// Parse all tokens from semicolon-separated string: "key1=val1; key2=val2; namespace:key3=val3"
-    pub fn parse(s: &str) -> Result<Vec<Self>, String> {
-        let parts = crate::parser::split::smart_split_semicolons(s)
-            .ok_or_else(|| "Unbalanced quotes in token string".to_string())?;
+    ///
+    /// Errors report the byte offset of the problem within `s`: the start of an
+    /// empty key, or the end of a token that is missing its `=` separator.
+    pub fn parse(s: &str) -> Result<Vec<Self>, MeteorError> {
+        let parts = crate::parser::split::smart_split_semicolons(s).ok_or_else(|| {
+            MeteorError::parse(
+                s.rfind('"').unwrap_or(0),
+                "Unbalanced quotes in token string",
+            )
+        })?;
 
         let mut tokens = Vec::new();
 
@@ -87,53 +94,62 @@ impl Token {
             if trimmed.is_empty() {
                 continue;
             }
-            let token = Self::parse_single(trimmed)?;
+            // Segments are borrowed from `s`, so their offset is the pointer distance
+            let offset = (token_str.as_ptr() as usize - s.as_ptr() as usize)
+                + (token_str.len() - token_str.trim_start().len());
+            let token = Self::parse_single(trimmed, offset)?;
             tokens.push(token);
         }
 
         if tokens.is_empty() {
-            return Err("No valid tokens found".to_string());
+            return Err(MeteorError::empty("token string"));
         }
 
         Ok(tokens)
     }
 
     /// Parse the first token from a string (convenience method)
-    pub fn first(s: &str) -> Result<Self, String> {
+    pub fn first(s: &str) -> Result<Self, MeteorError> {
         let tokens = Self::parse(s)?;
         Ok(tokens.into_iter().next().unwrap()) // Safe because parse() ensures non-empty vec
     }
 
     /// Parse a single token from "key=value" or "namespace:key=value" format
-    fn parse_single(s: &str) -> Result<Self, String> {
-        let parts: Vec<&str> = s.splitn(2, '=').collect();
-        if parts.len() != 2 {
-            return Err(format!("Invalid token format: {}", s));
-        }
-
-        let key_part = parts[0].trim();
-        let value = parts[1];
+    ///
+    /// `offset` is the byte position of `s` within the original input.
+    fn parse_single(s: &str, offset: usize) -> Result<Self, MeteorError> {
+        let Some((raw_key, value)) = s.split_once('=') else {
+            return Err(MeteorError::parse(
+                offset + s.len(),
+                format!("Missing '=' separator in token: {}", s),
+            ));
+        };
+
+        let key_part = raw_key.trim();
+        let key_offset = offset + (raw_key.len() - raw_key.trim_start().len());
 
         // Check if key_part contains namespace prefix
-        if key_part.contains(':') {
-            let key_parts: Vec<&str> = key_part.splitn(2, ':').collect();
-            if key_parts.len() == 2 {
-                let namespace = Namespace::from_string(key_parts[0]);
-                let key = key_parts[1];
-
-                // Key cannot be empty
-                if key.is_empty() {
-                    return Err(format!("Token key cannot be empty: {}", s));
-                }
-
-                Ok(Token::new_with_namespace(namespace, key, value))
-            } else {
-                return Err(format!("Invalid namespaced token format: {}", s));
+        if let Some((namespace, key)) = key_part.split_once(':') {
+            // Key cannot be empty
+            if key.is_empty() {
+                return Err(MeteorError::parse(
+                    key_offset + namespace.len() + 1,
+                    format!("Token key cannot be empty: {}", s),
+                ));
             }
+
+            Ok(Token::new_with_namespace(
+                Namespace::from_string(namespace),
+                key,
+                value,
+            ))
         } else {
             // Key cannot be empty
             if key_part.is_empty() {
-                return Err(format!("Token key cannot be empty: {}", s));
+                return Err(MeteorError::parse(
+                    key_offset,
+                    format!("Token key cannot be empty: {}", s),
+                ));
             }
 
             Ok(Token::new(key_part, value))
@@ -157,7 +173,7 @@ impl fmt::Display for Token {
 }
 
 impl FromStr for Token {
-    type Err = String;
+    type Err = MeteorError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Token::first(s)
@@ -225,4 +241,29 @@ mod tests {
     fn test_token_parse_unbalanced_quotes() {
         assert!(Token::parse("message=\"Hello; World").is_err());
     }
+
+    #[test]
+    fn test_token_parse_reports_empty_key_offset() {
+        let err = Token::parse("=value").unwrap_err();
+        assert!(matches!(err, MeteorError::ParseError { position: 0, .. }));
+
+        let err = Token::parse("ui:=value").unwrap_err();
+        assert!(matches!(err, MeteorError::ParseError { position: 3, .. }));
+    }
+
+    #[test]
+    fn test_token_parse_reports_missing_separator_offset() {
+        let err = Token::parse("novalue").unwrap_err();
+        assert!(matches!(err, MeteorError::ParseError { position: 7, .. }));
+        assert!(err.to_string().contains("Missing '='"));
+    }
+
+    #[test]
+    fn test_token_parse_offsets_are_relative_to_input() {
+        let err = Token::parse("theme=dark;  =oops").unwrap_err();
+        assert!(matches!(err, MeteorError::ParseError { position: 13, .. }));
+
+        let err = Token::parse("theme=dark; broken").unwrap_err();
+        assert!(matches!(err, MeteorError::ParseError { position: 18, .. }));
+    }
 }
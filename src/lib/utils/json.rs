@@ -0,0 +1,133 @@
+//! Incremental JSON array output
+//!
+//! `JsonArrayWriter` streams one element at a time to any `io::Write`
+//! sink, so large listings never need to be assembled into a single
+//! document first. Element encoding goes through `serde_json`, which
+//! takes care of escaping quotes, backslashes and control characters.
+
+use serde_json::Value;
+use std::io::{self, Write};
+
+/// Streaming writer for a top-level JSON array.
+///
+/// The opening bracket is written by [`JsonArrayWriter::new`] and the
+/// closing bracket by [`JsonArrayWriter::finish`]; each pushed element
+/// is written immediately. Call [`JsonArrayWriter::flush`] to push it
+/// through a buffered sink before the array is finished.
+pub struct JsonArrayWriter<W: Write> {
+    writer: W,
+    count: usize,
+}
+
+impl<W: Write> JsonArrayWriter<W> {
+    /// Start a new array on `writer`
+    pub fn new(mut writer: W) -> io::Result<Self> {
+        writer.write_all(b"[")?;
+        Ok(Self { writer, count: 0 })
+    }
+
+    /// Write one element
+    pub fn push(&mut self, value: &Value) -> io::Result<()> {
+        self.separator()?;
+        serde_json::to_writer(&mut self.writer, value).map_err(io::Error::from)?;
+        self.count += 1;
+        Ok(())
+    }
+
+    /// Write an object element built from string fields, in the given order
+    ///
+    /// Fields are written directly rather than through a `serde_json::Map`,
+    /// which would sort them by name.
+    pub fn push_fields(&mut self, fields: &[(&str, &str)]) -> io::Result<()> {
+        self.separator()?;
+        self.writer.write_all(b"{")?;
+        for (index, (name, value)) in fields.iter().enumerate() {
+            if index > 0 {
+                self.writer.write_all(b",")?;
+            }
+            serde_json::to_writer(&mut self.writer, name).map_err(io::Error::from)?;
+            self.writer.write_all(b":")?;
+            serde_json::to_writer(&mut self.writer, value).map_err(io::Error::from)?;
+        }
+        self.writer.write_all(b"}")?;
+        self.count += 1;
+        Ok(())
+    }
+
+    /// Write the separator that precedes the next element
+    fn separator(&mut self) -> io::Result<()> {
+        self.writer
+            .write_all(if self.count == 0 { b"\n  " } else { b",\n  " })
+    }
+
+    /// Number of elements written so far
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Check if no elements have been written
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Flush the underlying writer
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Close the array and return the underlying writer
+    pub fn finish(mut self) -> io::Result<W> {
+        self.writer
+            .write_all(if self.count == 0 { b"]\n" } else { b"\n]\n" })?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_array() {
+        let out = JsonArrayWriter::new(Vec::new()).unwrap().finish().unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "[]\n");
+    }
+
+    #[test]
+    fn test_escapes_quotes_and_newlines() {
+        let mut writer = JsonArrayWriter::new(Vec::new()).unwrap();
+        writer
+            .push_fields(&[("key", "say \"hi\""), ("value", "line1\nline2\t\\end")])
+            .unwrap();
+        writer
+            .push_fields(&[("key", "plain"), ("value", "ok")])
+            .unwrap();
+        assert_eq!(writer.len(), 2);
+        let out = writer.finish().unwrap();
+
+        let parsed: Value = serde_json::from_slice(&out).unwrap();
+        let items = parsed.as_array().unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0]["key"], "say \"hi\"");
+        assert_eq!(items[0]["value"], "line1\nline2\t\\end");
+        assert_eq!(items[1]["key"], "plain");
+    }
+
+    #[test]
+    fn test_push_fields_keeps_field_order() {
+        let mut writer = JsonArrayWriter::new(Vec::new()).unwrap();
+        writer
+            .push_fields(&[
+                ("path", "app:ui:theme"),
+                ("key", "theme"),
+                ("context", "app"),
+            ])
+            .unwrap();
+        let out = String::from_utf8(writer.finish().unwrap()).unwrap();
+        assert_eq!(
+            out,
+            "[\n  {\"path\":\"app:ui:theme\",\"key\":\"theme\",\"context\":\"app\"}\n]\n"
+        );
+    }
+}
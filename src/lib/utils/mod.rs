@@ -4,8 +4,10 @@
 //! the core functionality including validation helpers and access utilities.
 
 pub mod access;
+pub mod json;
 pub mod validators;
 
+pub use json::JsonArrayWriter;
 pub use validators::{
     is_valid_meteor_format, is_valid_meteor_shower_format, is_valid_token_format,
 };
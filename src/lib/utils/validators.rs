@@ -3,7 +3,7 @@
 //! This module provides low-level string format validation without parsing.
 //! These validators check syntax and format correctness only.
 
-use crate::types::METEOR_DELIMITER;
+use crate::types::{MeteorError, METEOR_DELIMITER};
 
 /// Validate token string format: "key=value"
 ///
@@ -191,6 +191,82 @@ fn has_consecutive_semicolons_outside_quotes(s: &str) -> bool {
     false
 }
 
+/// Validate characters of a key, context or namespace for strict storage
+///
+/// Rejects control characters and unescaped `=`, `;` or `:`; a preceding
+/// backslash escapes the next character. Dots are allowed so dotted keys and
+/// namespaces keep working. The error reports the offending character, its
+/// byte position and the `component` being checked.
+///
+/// # Examples
+/// ```
+/// use meteor::utils::validators::validate_strict_chars;
+///
+/// assert!(validate_strict_chars("ui.widgets", "namespace").is_ok());
+/// assert!(validate_strict_chars("a\\=b", "key").is_ok());          // escaped '='
+/// assert!(validate_strict_chars("a=b", "key").is_err());
+/// assert!(validate_strict_chars("tab\there", "key").is_err());     // control character
+/// ```
+pub fn validate_strict_chars(s: &str, component: &str) -> Result<(), MeteorError> {
+    let mut escaped = false;
+    for (position, ch) in s.char_indices() {
+        if ch.is_control() {
+            return Err(MeteorError::invalid_char(
+                ch,
+                position,
+                format!("{} '{}'", component, s.escape_debug()),
+            ));
+        }
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match ch {
+            '\\' => escaped = true,
+            '=' | ';' | ':' => {
+                return Err(MeteorError::invalid_char(
+                    ch,
+                    position,
+                    format!("{} '{}'", component, s.escape_debug()),
+                ))
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Validate segments before they are joined into a `context:namespace:key` path
+///
+/// A `:` inside any segment would shift the remaining segments when the
+/// joined path is parsed, so it is rejected rather than silently storing the
+/// value somewhere else. Empty segments are allowed; an empty namespace
+/// still produces a valid `context::key` path.
+///
+/// # Examples
+/// ```
+/// use meteor::utils::validators::validate_path_segments;
+///
+/// assert!(validate_path_segments("app", "ui.widgets", "list[0]").is_ok());
+/// assert!(validate_path_segments("app", "ui:main", "theme").is_err());
+/// ```
+pub fn validate_path_segments(
+    context: &str,
+    namespace: &str,
+    key: &str,
+) -> Result<(), MeteorError> {
+    for (segment, component) in [(context, "context"), (namespace, "namespace"), (key, "key")] {
+        if let Some(position) = segment.find(':') {
+            return Err(MeteorError::invalid_char(
+                ':',
+                position,
+                format!("{} segment '{}'", component, segment.escape_debug()),
+            ));
+        }
+    }
+    Ok(())
+}
+
 /// Helper: Validate a token within meteor context
 ///
 /// Handles both full context:namespace:key=value and simple key=value formats
@@ -415,4 +491,18 @@ mod tests {
             "message=\"hello; world\";; theme=dark"
         ));
     }
+
+    #[test]
+    fn test_validate_strict_chars() {
+        assert!(validate_strict_chars("button", "key").is_ok());
+        assert!(validate_strict_chars("list[0]", "key").is_ok());
+        assert!(validate_strict_chars("a\\;b", "key").is_ok());
+
+        let err = validate_strict_chars("a=b", "key").unwrap_err();
+        assert_eq!(err, MeteorError::invalid_char('=', 1, "key 'a=b'"));
+
+        assert!(validate_strict_chars("x;y", "key").is_err());
+        assert!(validate_strict_chars("ctx:x", "context").is_err());
+        assert!(validate_strict_chars("bell\u{7}", "key").is_err());
+    }
 }
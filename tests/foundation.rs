@@ -461,15 +461,14 @@ mod end_to_end_workflow_tests {
         )
         .unwrap();
 
-        // 4. Clean up sensitive data (command recorded but deletion not yet implemented)
+        // 4. Clean up sensitive data (dotted target resolves to app:db:pass)
         TokenStreamParser::process(&mut engine, "ctl:delete=app.db.pass").unwrap();
 
         // Verify final state
         assert_eq!(engine.get("app:main:host"), Some("localhost"));
         assert_eq!(engine.get("app:main:port"), Some("8080"));
         assert_eq!(engine.get("app:db:user"), Some("admin"));
-        // TODO: Implement actual deletion in StorageData - currently delete is not implemented
-        assert_eq!(engine.get("app:db:pass"), Some("secret")); // Still there (delete not implemented)
+        assert_eq!(engine.get("app:db:pass"), None);
         assert_eq!(engine.get("user:db:name"), Some("Alice")); // Alice stored in user:db context
         assert_eq!(engine.get("sys:config:debug"), Some("true"));
 
@@ -1,6 +1,7 @@
 //! Test MeteorEngine hybrid storage integration
 
-use meteor::MeteorEngine;
+use meteor::types::SetWarning;
+use meteor::{MeteorEngine, MeteorError};
 
 #[test]
 fn test_meteor_engine_hybrid_storage_methods() {
@@ -93,3 +94,815 @@ fn test_meteor_engine_path_parser_regression() {
     );
     assert_eq!(engine.get_default("app:ui"), Some("default_ui"));
 }
+
+#[test]
+fn test_find_namespaces_with_key() {
+    let mut engine = MeteorEngine::new();
+    engine.set("prod:db:host", "db.internal").unwrap();
+    engine.set("dev:db:host", "localhost").unwrap();
+    engine.set("dev:cache:host", "127.0.0.1").unwrap();
+    engine.set("dev:cache:port", "6379").unwrap();
+    engine.set("dev:ui:list[0]", "first").unwrap();
+
+    assert_eq!(
+        engine.find_namespaces_with_key("host"),
+        vec![
+            ("dev".to_string(), "cache".to_string()),
+            ("dev".to_string(), "db".to_string()),
+            ("prod".to_string(), "db".to_string()),
+        ]
+    );
+    assert_eq!(
+        engine.find_namespaces_with_key("list[0]"),
+        vec![("dev".to_string(), "ui".to_string())]
+    );
+
+    // Present nowhere, and prefixes of stored keys do not match
+    assert!(engine.find_namespaces_with_key("password").is_empty());
+    assert!(engine.find_namespaces_with_key("ho").is_empty());
+}
+
+#[test]
+fn test_get_or_default_exact_key_wins() {
+    let mut engine = MeteorEngine::new();
+    engine.set("app:ui:index", "default_ui").unwrap();
+    engine.set("app:ui:button", "click").unwrap();
+
+    assert_eq!(engine.get_or_default("app:ui:button"), Some("click"));
+}
+
+#[test]
+fn test_get_or_default_falls_back_to_enclosing_default() {
+    let mut engine = MeteorEngine::new();
+    engine.set("app:ui:index", "default_ui").unwrap();
+    engine.set("app:ui:nav.index", "default_nav").unwrap();
+    engine.set("app:ui:nav.home", "/").unwrap();
+
+    // Nearest enclosing directory default wins
+    assert_eq!(
+        engine.get_or_default("app:ui:nav.missing"),
+        Some("default_nav")
+    );
+    // Top-level keys fall back to the namespace default
+    assert_eq!(engine.get_or_default("app:ui:missing"), Some("default_ui"));
+    // Directories without their own default walk up to the namespace
+    assert_eq!(
+        engine.get_or_default("app:ui:footer.links.missing"),
+        Some("default_ui")
+    );
+}
+
+#[test]
+fn test_get_or_default_without_default_is_none() {
+    let mut engine = MeteorEngine::new();
+    engine.set("app:ui:button", "click").unwrap();
+
+    assert_eq!(engine.get_or_default("app:ui:missing"), None);
+    assert_eq!(engine.get_or_default("app:ui:nav.missing"), None);
+    assert_eq!(engine.get_or_default("app:other:missing"), None);
+}
+
+#[test]
+fn test_get_or_default_resolves_aliases() {
+    let mut engine = MeteorEngine::new();
+    engine
+        .set_namespace_alias("widgets", ("app", "ui.widgets"))
+        .unwrap();
+    engine
+        .set("app:ui.widgets:index", "default_widget")
+        .unwrap();
+    engine.set("app:ui.widgets:button", "click").unwrap();
+
+    assert_eq!(engine.get_or_default("@widgets:button"), Some("click"));
+    assert_eq!(
+        engine.get_or_default("@widgets:missing"),
+        Some("default_widget")
+    );
+    assert_eq!(engine.get_or_default("@missing:button"), None);
+}
+
+#[test]
+fn test_existence_checks_survive_churn() {
+    let mut engine = MeteorEngine::new();
+
+    for round in 0..3 {
+        for i in 0..20 {
+            engine
+                .set(&format!("ctx{}:ns{}:key", i, i % 4), &round.to_string())
+                .unwrap();
+            engine
+                .set(&format!("ctx{}:ns{}:other", i, i % 4), "x")
+                .unwrap();
+        }
+        for i in (0..20).filter(|i| i % 2 == 0) {
+            engine.delete(&format!("ctx{}:ns{}:key", i, i % 4)).unwrap();
+        }
+        for i in (0..20).filter(|i| i % 3 == 0) {
+            engine.delete(&format!("ctx{}:ns{}:", i, i % 4)).unwrap();
+        }
+    }
+
+    for i in 0..20 {
+        let ns = format!("ns{}", i % 4);
+        let ctx = format!("ctx{}", i);
+        let expected = i % 3 != 0;
+        assert_eq!(
+            engine.namespace_exists_fast(&ctx, &ns),
+            expected,
+            "{}:{}",
+            ctx,
+            ns
+        );
+        assert_eq!(engine.context_exists_fast(&ctx), expected, "{}", ctx);
+        assert_eq!(!engine.namespace_is_empty(&ctx, &ns), expected);
+    }
+
+    // Removing the last key of a namespace makes it disappear
+    engine.delete("ctx1:ns1:other").unwrap();
+    engine.delete("ctx1:ns1:key").unwrap();
+    assert!(!engine.namespace_exists_fast("ctx1", "ns1"));
+    assert!(!engine.context_exists_fast("ctx1"));
+    assert!(!engine.namespace_exists_fast("missing", "ns1"));
+
+    engine.set("ctx1:ns1:key", "back").unwrap();
+    assert!(engine.namespace_exists_fast("ctx1", "ns1"));
+    assert!(engine.is_directory("ctx1:ns1"));
+}
+
+#[test]
+fn test_find_by_value_pattern() {
+    let mut engine = MeteorEngine::new();
+    engine.set("app:api:base", "http://example.com").unwrap();
+    engine
+        .set("app:api:docs", "https://docs.example.com")
+        .unwrap();
+    engine.set("user:links:list[0]", "http://a.test").unwrap();
+    engine.set("app:ui:theme", "dark").unwrap();
+    engine.set("app:ui:title", "{{PLACEHOLDER}}").unwrap();
+
+    assert_eq!(
+        engine.find_by_value_pattern("http://*"),
+        vec![
+            ("app:api:base".to_string(), "http://example.com".to_string()),
+            (
+                "user:links:list[0]".to_string(),
+                "http://a.test".to_string()
+            ),
+        ]
+    );
+    assert_eq!(
+        engine.find_by_value_pattern("{{*}}"),
+        vec![("app:ui:title".to_string(), "{{PLACEHOLDER}}".to_string())]
+    );
+    assert!(engine.find_by_value_pattern("ftp://*").is_empty());
+}
+
+#[test]
+fn test_list_children_mixes_directories_and_leaves() {
+    let mut engine = MeteorEngine::new();
+    engine.set("app:ui:theme", "dark").unwrap();
+    engine.set("app:ui:lang", "en").unwrap();
+    engine.set("app:ui.widgets:button", "click").unwrap();
+    engine.set("app:ui.widgets.forms:submit", "go").unwrap();
+    engine.set("app:ui.layout.grid:columns", "12").unwrap();
+    engine.set("app:uikit:version", "2").unwrap();
+    engine.set("app:main:debug", "false").unwrap();
+
+    assert_eq!(
+        engine.list_children("app", "ui"),
+        vec![
+            ("layout".to_string(), true),
+            ("widgets".to_string(), true),
+            ("theme".to_string(), false),
+            ("lang".to_string(), false),
+        ]
+    );
+    assert_eq!(
+        engine.list_children("app", "ui.widgets"),
+        vec![("forms".to_string(), true), ("button".to_string(), false)]
+    );
+    assert_eq!(
+        engine.list_children("app", ""),
+        vec![
+            ("main".to_string(), true),
+            ("ui".to_string(), true),
+            ("uikit".to_string(), true),
+        ]
+    );
+    assert!(engine.list_children("app", "missing").is_empty());
+
+    // Namespaces emptied by deletes no longer show up
+    engine.delete("app:ui.layout.grid:columns").unwrap();
+    assert!(!engine
+        .list_children("app", "ui")
+        .contains(&("layout".to_string(), true)));
+}
+
+#[test]
+fn test_rename_namespace_rebuilds_tree_index() {
+    let mut engine = MeteorEngine::new();
+    engine.set("app:ui.widgets:button.primary", "blue").unwrap();
+    engine
+        .set("app:ui.widgets:button.secondary", "grey")
+        .unwrap();
+    engine.set("app:ui.widgets:label", "Save").unwrap();
+    engine
+        .set("app:ui.widgets.forms:field.name", "text")
+        .unwrap();
+    engine.set("app:ui:theme", "dark").unwrap();
+
+    assert_eq!(
+        engine.rename_namespace("app", "ui.widgets", "ui.components"),
+        Ok(4)
+    );
+
+    assert!(engine.is_directory("app:ui.components"));
+    assert!(engine.is_directory("app:ui.components:button"));
+    assert!(engine.is_directory("app:ui.components.forms:field"));
+    assert!(engine.is_file("app:ui.components:button.primary"));
+
+    assert!(!engine.is_directory("app:ui.widgets"));
+    assert!(!engine.is_directory("app:ui.widgets:button"));
+    assert!(!engine.is_directory("app:ui.widgets.forms:field"));
+    assert!(!engine.is_file("app:ui.widgets:button.primary"));
+
+    assert_eq!(engine.get("app:ui.components:label"), Some("Save"));
+    assert_eq!(engine.get("app:ui:theme"), Some("dark"));
+    let view = engine.namespace_view("app", "ui.components").unwrap();
+    let keys: Vec<&str> = view.keys().collect();
+    assert_eq!(keys, vec!["button.primary", "button.secondary", "label"]);
+}
+
+#[test]
+fn test_rename_namespace_moves_metadata() {
+    let mut engine = MeteorEngine::new();
+    engine.set("app:ui.widgets:button", "click").unwrap();
+    engine.set("app:ui.widgets.forms:field", "text").unwrap();
+    engine.set_namespace_meta("app", "ui.widgets", "owner", "design");
+    engine.set_namespace_meta("app", "ui.widgets.forms", "owner", "forms");
+
+    engine
+        .rename_namespace("app", "ui.widgets", "ui.components")
+        .unwrap();
+
+    assert_eq!(
+        engine.get_namespace_meta("app", "ui.components", "owner"),
+        Some("design")
+    );
+    assert_eq!(
+        engine.get_namespace_meta("app", "ui.components.forms", "owner"),
+        Some("forms")
+    );
+    assert!(engine.namespace_meta("app", "ui.widgets").is_empty());
+    assert!(engine.namespace_meta("app", "ui.widgets.forms").is_empty());
+}
+
+#[test]
+fn test_rename_namespace_rejects_collisions() {
+    let mut engine = MeteorEngine::new();
+    engine.set("app:ui.widgets:button", "click").unwrap();
+    engine.set("app:ui.components:button", "tap").unwrap();
+
+    assert!(engine
+        .rename_namespace("app", "ui.widgets", "ui.components")
+        .is_err());
+    assert!(engine
+        .rename_namespace("app", "ui.widgets", "ui.widgets.inner")
+        .is_err());
+    assert_eq!(engine.get("app:ui.widgets:button"), Some("click"));
+    assert_eq!(engine.rename_namespace("app", "missing", "other"), Ok(0));
+}
+
+#[test]
+fn test_observe_namespace_only_sees_its_namespace() {
+    use meteor::types::NamespaceChange;
+    use std::sync::{Arc, Mutex};
+
+    let mut engine = MeteorEngine::new();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&seen);
+    let id = engine.observe_namespace("app", "ui", move |change| {
+        sink.lock().unwrap().push(change.clone())
+    });
+
+    engine.set("app:ui:theme", "dark").unwrap();
+    engine.set("app:main:debug", "true").unwrap();
+    engine.set("user:ui:theme", "light").unwrap();
+    engine.set("app:ui.widgets:button", "click").unwrap();
+    engine.set("app:ui:lang", "en").unwrap();
+    engine.delete("app:ui:theme").unwrap();
+    engine.delete("app:main:debug").unwrap();
+    engine.delete("app:ui:").unwrap();
+
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec![
+            NamespaceChange::Set {
+                key: "theme".to_string(),
+                value: "dark".to_string(),
+            },
+            NamespaceChange::Set {
+                key: "lang".to_string(),
+                value: "en".to_string(),
+            },
+            NamespaceChange::Removed {
+                key: "theme".to_string(),
+            },
+            NamespaceChange::Cleared,
+        ]
+    );
+
+    assert!(engine.remove_namespace_observer(id));
+    assert!(!engine.remove_namespace_observer(id));
+    engine.set("app:ui:theme", "dark").unwrap();
+    assert_eq!(seen.lock().unwrap().len(), 4);
+}
+
+#[test]
+fn test_observe_namespace_runs_after_the_write() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let mut engine = MeteorEngine::new();
+    engine.observe_namespace("app", "ui", |_| panic!("observer failed"));
+    let revision = engine.revision();
+
+    let result = catch_unwind(AssertUnwindSafe(|| engine.set("app:ui:theme", "dark")));
+
+    assert!(result.is_err());
+    assert_eq!(engine.get("app:ui:theme"), Some("dark"));
+    assert_eq!(engine.revision(), revision + 1);
+}
+
+#[test]
+fn test_set_owned_matches_set() {
+    use meteor::types::NormalizeMode;
+
+    let mut by_ref = MeteorEngine::new();
+    let mut owned = MeteorEngine::new();
+    for (path, value) in [("app:ui:theme", "dark"), ("user:docs:list[0]", "first")] {
+        by_ref.set(path, value).unwrap();
+        owned.set_owned(path, value.to_string()).unwrap();
+        assert_eq!(owned.get(path), by_ref.get(path));
+    }
+    assert!(owned.set_owned("a:b:c:d", "bad".to_string()).is_err());
+
+    // Large owned values are moved into storage, not copied
+    let body = "payload ".repeat(512);
+    let expected = body.clone();
+    let buffer = body.as_ptr();
+    owned.set_owned("app:docs:body", body).unwrap();
+    let stored = owned.get("app:docs:body").unwrap();
+    assert_eq!(stored, expected);
+    assert_eq!(stored.as_ptr(), buffer);
+
+    // Normalization still applies to owned values
+    owned.set_value_normalization(NormalizeMode::TrimEnds);
+    owned
+        .set_owned("app:ui:label", "  Save  ".to_string())
+        .unwrap();
+    assert_eq!(owned.get("app:ui:label"), Some("Save"));
+}
+
+#[test]
+fn test_keys_matching_returns_notation_form() {
+    let mut engine = MeteorEngine::new();
+    engine.set("app:ui:list[1]", "b").unwrap();
+    engine.set("app:ui:theme", "dark").unwrap();
+    engine.set("app:ui:list[0]", "a").unwrap();
+    engine.set("app:ui:grid[2,3]", "cell").unwrap();
+    engine.set("app:other:list[0]", "x").unwrap();
+
+    assert_eq!(
+        engine.keys_matching("app", "ui", "list[*]"),
+        vec!["list[1]".to_string(), "list[0]".to_string()]
+    );
+    assert_eq!(
+        engine.keys_matching("app", "ui", "*"),
+        vec!["list[1]", "theme", "list[0]", "grid[2,3]"]
+    );
+    assert_eq!(
+        engine.keys_matching("app", "ui", "grid*"),
+        vec!["grid[2,3]"]
+    );
+    assert!(engine.keys_matching("app", "ui", "list__*").is_empty());
+    assert!(engine.keys_matching("app", "missing", "*").is_empty());
+}
+
+#[test]
+fn test_flat_operations_without_tree_index() {
+    let mut engine = MeteorEngine::new();
+    engine.set_tree_index_enabled(false);
+    assert!(!engine.tree_index_enabled());
+
+    engine.set("app:ui:button.primary", "blue").unwrap();
+    engine.set("app:ui:list[0]", "a").unwrap();
+    engine.set("user:settings:theme", "dark").unwrap();
+
+    assert_eq!(engine.get("app:ui:button.primary"), Some("blue"));
+    assert_eq!(engine.get("app:ui:list[0]"), Some("a"));
+    assert_eq!(
+        engine.find("app:ui:*"),
+        vec!["app:ui:button.primary", "app:ui:list[0]"]
+    );
+    assert_eq!(engine.namespaces_in_context("app"), vec!["ui".to_string()]);
+    assert!(engine.is_file("app:ui:button.primary"));
+    assert!(engine.is_directory("app:ui"));
+    assert!(!engine.is_directory("app:ui:button"));
+
+    assert_eq!(engine.delete("app:ui:button.primary"), Ok(true));
+    assert_eq!(engine.get("app:ui:button.primary"), None);
+    assert_eq!(engine.delete("user:settings:"), Ok(true));
+    assert!(engine.namespace_is_empty("user", "settings"));
+
+    engine.clear_storage();
+    assert!(!engine.tree_index_enabled());
+
+    engine.set("app:ui:button.primary", "blue").unwrap();
+    engine.set_tree_index_enabled(true);
+    assert!(engine.is_directory("app:ui:button"));
+}
+
+#[test]
+fn test_load_json_object_flattens_nesting() {
+    let mut engine = MeteorEngine::new();
+
+    let stored = engine
+        .load_json_object(
+            "app",
+            r#"{
+                "ui": {"button": "click", "theme": "dark"},
+                "db": {"primary": {"host": "localhost", "port": 5432}},
+                "features": {"tags": ["alpha", "beta"], "enabled": true},
+                "debug": false
+            }"#,
+        )
+        .unwrap();
+
+    assert_eq!(stored, 8);
+    assert_eq!(engine.get("app:ui:button"), Some("click"));
+    assert_eq!(engine.get("app:ui:theme"), Some("dark"));
+    assert_eq!(engine.get("app:db.primary:host"), Some("localhost"));
+    assert_eq!(engine.get("app:db.primary:port"), Some("5432"));
+    assert_eq!(engine.get("app:features:tags[0]"), Some("alpha"));
+    assert_eq!(engine.get("app:features:tags[1]"), Some("beta"));
+    assert_eq!(engine.get("app:features:enabled"), Some("true"));
+    assert_eq!(engine.get("app:main:debug"), Some("false"));
+    assert!(engine
+        .namespaces_in_context("app")
+        .contains(&"db.primary".to_string()));
+}
+
+#[test]
+fn test_load_json_object_rejects_bad_input_without_writing() {
+    let mut engine = MeteorEngine::new();
+
+    assert!(engine.load_json_object("app", "not json").is_err());
+    assert!(engine.load_json_object("app", r#"["a", "b"]"#).is_err());
+    assert!(engine
+        .load_json_object("app", r#"{"ui": {"ok": "1", "rows": [{"id": 1}]}}"#)
+        .is_err());
+    assert!(engine
+        .load_json_object("app", r#"{"ui": {"a:b": "1"}}"#)
+        .is_err());
+    assert!(engine.is_empty());
+
+    // Leaves are checked against the value validator before any write
+    engine.set_value_validator(|_, _, _, value| {
+        if value == "null" {
+            Err("null values are not allowed".to_string())
+        } else {
+            Ok(())
+        }
+    });
+    assert!(engine
+        .load_json_object("app", r#"{"ui": {"button": "click", "icon": null}}"#)
+        .is_err());
+    assert!(engine.is_empty());
+    engine.clear_value_validator();
+
+    // A `main` object and a top-level scalar both map to app:main:debug
+    let err = engine
+        .load_json_object("app", r#"{"main": {"debug": "1"}, "debug": "2"}"#)
+        .unwrap_err();
+    assert!(err.contains("app:main:debug"));
+    assert!(engine
+        .load_json_object(
+            "app",
+            r#"{"db.primary": {"host": "a"}, "db": {"primary": {"host": "b"}}}"#
+        )
+        .is_err());
+    assert!(engine
+        .load_json_object("app", r#"{"ui": {"tabs": ["a"], "tabs__i_0": "b"}}"#)
+        .is_err());
+    assert!(engine
+        .load_json_object("app", r#"{"ui..nav": {"home": "/"}}"#)
+        .is_err());
+    assert!(engine.is_empty());
+}
+
+#[test]
+fn test_load_json_object_accepts_dotted_member_names() {
+    let mut engine = MeteorEngine::new();
+
+    let stored = engine
+        .load_json_object(
+            "app",
+            r#"{
+                "db.replica": {"host": "b"},
+                "db": {"primary": {"host": "a"}},
+                "ui": {"nav.home": "/"}
+            }"#,
+        )
+        .unwrap();
+
+    assert_eq!(stored, 3);
+    assert_eq!(engine.get("app:db.replica:host"), Some("b"));
+    assert_eq!(engine.get("app:db.primary:host"), Some("a"));
+    assert_eq!(engine.get("app:ui:nav.home"), Some("/"));
+}
+
+#[test]
+fn test_to_json_object_round_trips_nested_json() {
+    let input = r#"{
+        "ui": {"button": "click", "tabs": ["home", "settings", "about"]},
+        "db": {"primary": {"host": "localhost", "port": "5432"}, "pool": "4"},
+        "debug": "false"
+    }"#;
+
+    let mut engine = MeteorEngine::new();
+    engine.load_json_object("app", input).unwrap();
+    engine.set("other:ui:button", "tap").unwrap();
+
+    let output = engine.to_json_object("app").unwrap();
+    let expected: serde_json::Value = serde_json::from_str(input).unwrap();
+    let actual: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(actual, expected);
+
+    assert_eq!(engine.to_json_object("missing").unwrap(), "{}");
+}
+
+#[test]
+fn test_to_json_object_escapes_values() {
+    let mut engine = MeteorEngine::new();
+    engine.set("app:ui:label", "say \"hi\"\nnow").unwrap();
+
+    let parsed: serde_json::Value =
+        serde_json::from_str(&engine.to_json_object("app").unwrap()).unwrap();
+    assert_eq!(parsed["ui"]["label"], "say \"hi\"\nnow");
+}
+
+#[test]
+fn test_to_json_object_bounds_sparse_indices() {
+    let mut engine = MeteorEngine::new();
+    engine.set("app:ui:tabs[0]", "home").unwrap();
+    engine.set("app:ui:tabs[2]", "about").unwrap();
+    engine.set("app:ui:rows[5000]", "far").unwrap();
+    engine
+        .set("app:ui:max[18446744073709551615]", "edge")
+        .unwrap();
+
+    let parsed: serde_json::Value =
+        serde_json::from_str(&engine.to_json_object("app").unwrap()).unwrap();
+    assert_eq!(
+        parsed["ui"]["tabs"],
+        serde_json::json!(["home", null, "about"])
+    );
+    assert_eq!(parsed["ui"]["rows[5000]"], "far");
+    assert_eq!(parsed["ui"]["max[18446744073709551615]"], "edge");
+    assert!(parsed["ui"].get("rows").is_none());
+}
+
+#[test]
+fn test_to_json_object_rejects_colliding_members() {
+    let mut engine = MeteorEngine::new();
+    engine.set("app:ui:tabs", "plain").unwrap();
+    engine.set("app:ui:tabs[0]", "home").unwrap();
+
+    let err = engine.to_json_object("app").unwrap_err();
+    assert_eq!(
+        err,
+        MeteorError::key_collision("ui.tabs", "app:ui:tabs", "app:ui:tabs[0]")
+    );
+
+    let mut engine = MeteorEngine::new();
+    engine.set("app:main:ui", "leaf").unwrap();
+    engine.set("app:ui:button", "click").unwrap();
+    assert!(matches!(
+        engine.to_json_object("app"),
+        Err(MeteorError::KeyCollision { .. })
+    ));
+
+    // Dots inside a key are not member boundaries
+    let mut engine = MeteorEngine::new();
+    engine.set("app:main:config.json", "{}").unwrap();
+    engine.set("app:config:json", "yes").unwrap();
+    let parsed: serde_json::Value =
+        serde_json::from_str(&engine.to_json_object("app").unwrap()).unwrap();
+    assert_eq!(parsed["config.json"], "{}");
+    assert_eq!(parsed["config"]["json"], "yes");
+}
+
+#[test]
+fn test_find_paged_windows() {
+    let mut engine = MeteorEngine::new();
+    for key in ["e", "c", "a", "d", "b"] {
+        engine.set(&format!("app:ui:{}", key), "1").unwrap();
+    }
+    engine.set("app:other:z", "1").unwrap();
+
+    assert_eq!(engine.count_matching("app:ui:*"), 5);
+    assert_eq!(
+        engine.find_paged("app:ui:*", 0, 2),
+        vec!["app:ui:a", "app:ui:b"]
+    );
+    assert_eq!(
+        engine.find_paged("app:ui:*", 2, 2),
+        vec!["app:ui:c", "app:ui:d"]
+    );
+    assert_eq!(engine.find_paged("app:ui:*", 4, 2), vec!["app:ui:e"]);
+    assert_eq!(engine.find_paged("app:ui:*", 0, 10).len(), 5);
+    assert!(engine.find_paged("app:ui:*", 0, 0).is_empty());
+    assert!(engine.find_paged("app:ui:*", 5, 2).is_empty());
+    assert!(engine.find_paged("app:ui:*", 100, 2).is_empty());
+}
+
+#[test]
+fn test_context_storage_direct_queries() {
+    let mut engine = MeteorEngine::new();
+    engine.set("app:ui.widgets:button.primary", "blue").unwrap();
+    engine
+        .set("app:ui.widgets:button.secondary", "grey")
+        .unwrap();
+    engine.set("app:ui.widgets:label", "Save").unwrap();
+    engine.set("user:main:name", "ada").unwrap();
+
+    let app = engine.context_storage("app").unwrap();
+    assert_eq!(
+        app.find_keys("ui.widgets", "button*"),
+        vec!["button.primary", "button.secondary"]
+    );
+    assert!(app.is_directory("ui.widgets", "button"));
+    assert!(!app.is_directory("ui.widgets", "label"));
+    assert!(app.is_file("ui.widgets", "label"));
+    assert_eq!(app.get("ui.widgets", "button.primary"), Some("blue"));
+    assert_eq!(app.get("main", "name"), None);
+
+    assert!(engine.context_storage("user").is_some());
+    assert!(engine.context_storage("system").is_none());
+}
+
+#[test]
+fn test_set_parts_matches_set() {
+    let cases = [
+        ("app", "ui", "theme", "dark"),
+        ("app", "ui.widgets", "list[0]", "first"),
+        ("app", "ui", "grid[2,3]", "cell"),
+        ("app", "", "root_key", "top"),
+    ];
+
+    let mut by_path = MeteorEngine::new();
+    let mut by_parts = MeteorEngine::new();
+    for (context, namespace, key, value) in cases {
+        by_path
+            .set(&format!("{}:{}:{}", context, namespace, key), value)
+            .unwrap();
+        by_parts.set_parts(context, namespace, key, value).unwrap();
+    }
+
+    let path_entries: Vec<_> = by_path.iter_entries().collect();
+    let parts_entries: Vec<_> = by_parts.iter_entries().collect();
+    assert_eq!(path_entries, parts_entries);
+    assert_eq!(by_parts.get("app::root_key"), Some("top"));
+    assert_eq!(by_parts.get("app:ui.widgets:list[0]"), Some("first"));
+}
+
+#[test]
+fn test_set_parts_validates_segments_and_resolves_aliases() {
+    let mut engine = MeteorEngine::new();
+    assert!(engine.set_parts("app", "ui:x", "key", "v").is_err());
+    assert!(engine.set_parts("app", "ui", "a:b", "v").is_err());
+    assert!(engine.set_parts("app", "ui", "", "v").is_err());
+    assert!(engine.is_empty());
+
+    engine
+        .set_namespace_alias("widgets", ("app", "ui.widgets"))
+        .unwrap();
+    engine.set_parts("@widgets", "", "button", "click").unwrap();
+    engine
+        .set_parts("@widgets", "forms", "submit", "send")
+        .unwrap();
+    assert_eq!(engine.get("app:ui.widgets:button"), Some("click"));
+    assert_eq!(engine.get("app:ui.widgets.forms:submit"), Some("send"));
+    assert!(engine.set_parts("@missing", "", "key", "v").is_err());
+}
+
+#[test]
+fn test_revision_tracks_mutations_only() {
+    let mut engine = MeteorEngine::new();
+    assert_eq!(engine.revision(), 0);
+
+    engine.set("app:ui:theme", "dark").unwrap();
+    engine.set("app:ui:lang", "en").unwrap();
+    let after_sets = engine.revision();
+    assert_eq!(after_sets, 2);
+
+    // Pure reads leave the revision alone
+    let _ = engine.get("app:ui:theme");
+    let _ = engine.find("app:ui:*");
+    let _ = engine.iter_entries().count();
+    let _ = engine
+        .namespace_view("app", "ui")
+        .map(|view| view.keys().count());
+    assert_eq!(engine.revision(), after_sets);
+
+    assert!(engine.delete("app:ui:theme").unwrap());
+    assert_eq!(engine.revision(), after_sets + 1);
+
+    // Deleting a missing key changes nothing
+    assert!(!engine.delete("app:ui:missing").unwrap());
+    assert_eq!(engine.revision(), after_sets + 1);
+
+    engine.reset_all();
+    assert_eq!(engine.revision(), after_sets + 2);
+}
+
+#[test]
+fn test_intern_values_keeps_reads_correct() {
+    let mut engine = MeteorEngine::new();
+    let values = ["true", "false", "pending"];
+    for i in 0..300 {
+        let context = if i % 2 == 0 { "app" } else { "user" };
+        engine
+            .set(&format!("{}:flags:flag{}", context, i), values[i % 3])
+            .unwrap();
+    }
+
+    assert_eq!(engine.intern_values(), 300 - values.len());
+    assert_eq!(engine.intern_values(), 0);
+
+    for i in 0..300 {
+        let context = if i % 2 == 0 { "app" } else { "user" };
+        assert_eq!(
+            engine.get(&format!("{}:flags:flag{}", context, i)),
+            Some(values[i % 3])
+        );
+    }
+
+    // Overwriting one shared value leaves the others intact
+    engine.set("app:flags:flag0", "changed").unwrap();
+    assert_eq!(engine.get("app:flags:flag0"), Some("changed"));
+    assert_eq!(engine.get("app:flags:flag6"), Some("true"));
+    assert_eq!(engine.get("user:flags:flag3"), Some("true"));
+}
+
+#[test]
+fn test_set_checked_plain_key_has_no_warnings() {
+    let mut engine = MeteorEngine::new();
+    assert!(engine
+        .set_checked("app:ui:theme", "dark")
+        .unwrap()
+        .is_empty());
+    assert!(engine
+        .set_checked("app:ui:nav.home", "home")
+        .unwrap()
+        .is_empty());
+    assert_eq!(engine.get("app:ui:theme"), Some("dark"));
+}
+
+#[test]
+fn test_set_checked_warns_on_defaults() {
+    let mut engine = MeteorEngine::new();
+
+    let warnings = engine.set_checked("app:ui:index", "root").unwrap();
+    assert_eq!(
+        warnings,
+        vec![SetWarning::SetsDefault {
+            context: "app".to_string(),
+            namespace: "ui".to_string(),
+            directory: String::new(),
+        }]
+    );
+    assert_eq!(engine.get_default("app:ui"), Some("root"));
+
+    // A plain key next to its directory default, in either order
+    engine.set("app:ui:nav", "menu").unwrap();
+    let shadow = SetWarning::ShadowsDefault {
+        context: "app".to_string(),
+        namespace: "ui".to_string(),
+        key: "nav".to_string(),
+        default_key: "nav.index".to_string(),
+    };
+    let warnings = engine.set_checked("app:ui:nav.index", "home").unwrap();
+    assert_eq!(warnings.len(), 2);
+    assert!(
+        matches!(&warnings[0], SetWarning::SetsDefault { directory, .. } if directory == "nav")
+    );
+    assert_eq!(warnings[1], shadow);
+
+    let warnings = engine.set_checked("app:ui:nav", "menu2").unwrap();
+    assert_eq!(warnings, vec![shadow]);
+    assert_eq!(engine.get("app:ui:nav"), Some("menu2"));
+    assert_eq!(engine.get("app:ui:nav.index"), Some("home"));
+
+    assert!(engine.set_checked("a:b:c:d", "x").is_err());
+}
@@ -1,4 +1,4 @@
-use meteor::types::MeteorEngine;
+use meteor::types::{ExportFormat, MeteorEngine};
 
 #[test]
 fn test_contexts_iter_empty() {
@@ -248,3 +248,339 @@ fn test_iter_entries_preserves_workspace_order_after_updates() {
 
     assert_eq!(entries[0].3, "updated");
 }
+
+#[test]
+fn test_meteors_in_context_scoped() {
+    let mut engine = MeteorEngine::new();
+    engine.set("app:main:key1", "value1").unwrap();
+    engine.set("app:ui:button", "click").unwrap();
+    engine.set("user:settings:theme", "dark").unwrap();
+    engine.set("user:profile:name", "ada").unwrap();
+    engine.set("system:config:port", "8080").unwrap();
+
+    let meteors: Vec<_> = engine.meteors_in_context("user").collect();
+    assert_eq!(meteors.len(), 2);
+    assert!(meteors.iter().all(|m| m.context().name() == "user"));
+
+    let namespaces: Vec<String> = meteors.iter().map(|m| m.namespace().to_string()).collect();
+    assert_eq!(namespaces, vec!["profile", "settings"]);
+}
+
+#[test]
+fn test_meteors_in_context_unknown() {
+    let mut engine = MeteorEngine::new();
+    engine.set("app:main:key1", "value1").unwrap();
+
+    assert_eq!(engine.meteors_in_context("missing").count(), 0);
+}
+
+#[test]
+fn test_export_iter_matches_export_all() {
+    let mut engine = MeteorEngine::new();
+    engine.set("app:main:key1", "value1").unwrap();
+    engine.set("app:ui:button", "click").unwrap();
+    engine.set("app:ui:theme", "dark").unwrap();
+    engine.set("user:settings:lang", "en").unwrap();
+    engine.set("user:empty:gone", "x").unwrap();
+    engine.delete("user:empty:gone").unwrap();
+
+    let streamed: Vec<_> = engine.export_iter(ExportFormat::Json).collect();
+
+    // One item per non-empty namespace, each identical to a direct export
+    assert_eq!(streamed.len(), 3);
+    assert_eq!(engine.export_all(ExportFormat::Json).len(), 3);
+    for lazy in &streamed {
+        let eager = engine
+            .export_namespace(&lazy.context, &lazy.namespace, ExportFormat::Json)
+            .unwrap();
+        assert_eq!(lazy.context, eager.context);
+        assert_eq!(lazy.namespace, eager.namespace);
+        assert_eq!(lazy.tokens, eager.tokens);
+        assert_eq!(lazy.metadata.checksum, eager.metadata.checksum);
+        assert_eq!(lazy.format, ExportFormat::Json);
+    }
+
+    let names: Vec<(String, String)> = streamed
+        .iter()
+        .map(|e| (e.context.clone(), e.namespace.clone()))
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            ("app".to_string(), "main".to_string()),
+            ("app".to_string(), "ui".to_string()),
+            ("user".to_string(), "settings".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_export_iter_empty_engine() {
+    let engine = MeteorEngine::new();
+    assert_eq!(engine.export_iter(ExportFormat::Text).count(), 0);
+    assert!(engine.export_all(ExportFormat::Text).is_empty());
+}
+
+#[test]
+fn test_iter_entries_in_matches_whole_segments() {
+    let mut engine = MeteorEngine::new();
+    engine.set("doc:guides:overview", "Start here").unwrap();
+    engine.set("doc:guides.install:intro", "Welcome").unwrap();
+    engine.set("doc:guides.install:setup", "Step 1").unwrap();
+    engine.set("doc:guidesX:stray", "no").unwrap();
+    engine.set("doc:reference:api", "v1").unwrap();
+    engine.set("app:guides:other", "no").unwrap();
+
+    let entries: Vec<(String, String)> = engine
+        .iter_entries_in("doc", "guides")
+        .map(|(_, namespace, key, _)| (namespace, key))
+        .collect();
+
+    assert_eq!(
+        entries,
+        vec![
+            ("guides".to_string(), "overview".to_string()),
+            ("guides.install".to_string(), "intro".to_string()),
+            ("guides.install".to_string(), "setup".to_string()),
+        ]
+    );
+    assert!(engine
+        .iter_entries_in("doc", "guides")
+        .all(|(context, _, _, _)| context == "doc"));
+    assert_eq!(engine.iter_entries_in("doc", "").count(), 5);
+    assert_eq!(engine.iter_entries_in("missing", "guides").count(), 0);
+}
+
+#[test]
+fn test_verify_export_flags_only_drifted_namespace() {
+    let mut engine = MeteorEngine::new();
+    engine.set("app:ui:theme", "dark").unwrap();
+    engine.set("app:main:title", "Home").unwrap();
+    engine.set("user:settings:lang", "en").unwrap();
+
+    let backup = engine.export_all(ExportFormat::Text);
+    assert!(engine.verify_export(&backup).iter().all(|(_, _, ok)| *ok));
+
+    engine.set("app:ui:theme", "light").unwrap();
+    assert_eq!(
+        engine.verify_export(&backup),
+        vec![
+            ("app".to_string(), "main".to_string(), true),
+            ("app".to_string(), "ui".to_string(), false),
+            ("user".to_string(), "settings".to_string(), true),
+        ]
+    );
+
+    engine.delete("user:settings:").unwrap();
+    assert!(!engine.verify_export(&backup)[2].2);
+}
+
+#[test]
+fn test_namespace_view_repeated_reads_match_fresh_views() {
+    let mut engine = MeteorEngine::new();
+    engine.set("app:ui:zebra", "z").unwrap();
+    engine.set("app:ui:list[0]", "first").unwrap();
+    engine.set("app:ui:apple", "a").unwrap();
+
+    let view = engine.namespace_view("app", "ui").unwrap();
+    let first: Vec<(String, String)> = view.entries().collect();
+    let second: Vec<(String, String)> = view.entries().collect();
+    assert_eq!(first, second);
+    assert_eq!(
+        first,
+        vec![
+            ("zebra".to_string(), "z".to_string()),
+            ("list[0]".to_string(), "first".to_string()),
+            ("apple".to_string(), "a".to_string()),
+        ]
+    );
+
+    for (key, value) in &first {
+        let fresh = engine.namespace_view("app", "ui").unwrap();
+        assert_eq!(view.get(key).as_deref(), Some(value.as_str()));
+        assert_eq!(fresh.get(key), view.get(key));
+    }
+    assert_eq!(
+        view.values().collect::<Vec<_>>(),
+        engine
+            .namespace_view("app", "ui")
+            .unwrap()
+            .values()
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(view.get("missing"), None);
+}
+
+#[test]
+fn test_namespace_meta_is_kept_out_of_data() {
+    let mut engine = MeteorEngine::new();
+    engine.set("app:ui:theme", "dark").unwrap();
+    engine.set_namespace_meta("app", "ui", "owner", "design");
+    engine.set_namespace_meta("app", "ui", "version", "1");
+    engine.set_namespace_meta("app", "ui", "version", "2");
+
+    assert_eq!(
+        engine.get_namespace_meta("app", "ui", "owner"),
+        Some("design")
+    );
+    assert_eq!(engine.get_namespace_meta("app", "ui", "version"), Some("2"));
+    assert_eq!(engine.get_namespace_meta("app", "main", "owner"), None);
+    assert_eq!(
+        engine.namespace_meta("app", "ui"),
+        &[
+            ("owner".to_string(), "design".to_string()),
+            ("version".to_string(), "2".to_string())
+        ]
+    );
+
+    let entries: Vec<_> = engine.iter_entries().collect();
+    assert_eq!(entries.len(), 1);
+    let view = engine.namespace_view("app", "ui").unwrap();
+    let keys: Vec<&str> = view.keys().collect();
+    assert_eq!(keys, vec!["theme"]);
+    assert_eq!(engine.get("app:ui:owner"), None);
+
+    // Metadata is independent of the keys it describes
+    engine.delete("app:ui:theme").unwrap();
+    assert_eq!(
+        engine.get_namespace_meta("app", "ui", "owner"),
+        Some("design")
+    );
+    assert!(engine.remove_namespace_meta("app", "ui", "owner"));
+    assert!(!engine.remove_namespace_meta("app", "ui", "owner"));
+}
+
+#[test]
+fn test_namespace_meta_travels_with_export() {
+    let mut source = MeteorEngine::new();
+    source.set("app:ui:theme", "dark").unwrap();
+    source.set_namespace_meta("app", "ui", "owner", "design");
+
+    let plain = source
+        .export_namespace("app", "ui", ExportFormat::Text)
+        .unwrap();
+    assert!(plain.annotations.is_empty());
+
+    let export = source
+        .export_namespace_with_meta("app", "ui", ExportFormat::Text)
+        .unwrap();
+    assert_eq!(export.metadata.checksum, plain.metadata.checksum);
+    let text = export.to_text();
+    assert!(text.contains("# Meta: owner=design"));
+
+    let parsed = meteor::types::ExportData::from_text(&text).unwrap();
+    let mut target = MeteorEngine::new();
+    let result = target.import_namespace(parsed).unwrap();
+    assert!(result.checksum_valid);
+    assert_eq!(target.get("app:ui:theme"), Some("dark"));
+    assert_eq!(
+        target.get_namespace_meta("app", "ui", "owner"),
+        Some("design")
+    );
+    assert_eq!(target.get("app:ui:owner"), None);
+}
+
+#[test]
+fn test_context_shower_holds_only_target_context() {
+    let mut engine = MeteorEngine::new();
+    engine.set("app:ui:button", "click").unwrap();
+    engine.set("user:settings:theme", "dark").unwrap();
+    engine.set("user:settings:lang", "en").unwrap();
+    engine.set("user:profile:name", "ada").unwrap();
+    engine.set("system:config:port", "8080").unwrap();
+
+    let shower = engine.context_shower("user");
+    assert_eq!(shower.len(), 2);
+    assert_eq!(shower.contexts(), vec!["user"]);
+    let mut namespaces = shower.namespaces_in_context("user");
+    namespaces.sort();
+    assert_eq!(namespaces, vec!["profile", "settings"]);
+
+    let settings = shower.by_context_namespace("user", "settings");
+    assert_eq!(settings.len(), 1);
+    let tokens: Vec<(String, String)> = settings[0]
+        .tokens()
+        .iter()
+        .map(|token| (token.key().to_string(), token.value().to_string()))
+        .collect();
+    assert_eq!(
+        tokens,
+        vec![
+            ("theme".to_string(), "dark".to_string()),
+            ("lang".to_string(), "en".to_string())
+        ]
+    );
+    assert!(shower.find("user", "profile", "name").is_some());
+    assert!(shower.find("app", "ui", "button").is_none());
+
+    assert!(engine.context_shower("missing").is_empty());
+}
+
+#[test]
+fn test_append_to_shower_merges_namespaces() {
+    let mut shower =
+        meteor::types::MeteorShower::parse("app:ui:button=click;theme=dark :;: doc:guide:intro=Hi")
+            .unwrap();
+
+    let mut first = MeteorEngine::new();
+    first.set("app:ui:theme", "light").unwrap();
+    first.set("app:ui:lang", "en").unwrap();
+    first.set("user:prefs:font", "mono").unwrap();
+    first.append_to_shower(&mut shower);
+
+    let mut second = MeteorEngine::new();
+    second.set("user:prefs:font", "serif").unwrap();
+    second.append_to_shower(&mut shower);
+
+    assert_eq!(shower.len(), 3);
+    assert_eq!(
+        shower.to_string(),
+        "app:ui:button=click;theme=light;lang=en :;: doc:guide:intro=Hi :;: user:prefs:font=serif"
+    );
+}
+
+#[test]
+fn test_value_histogram_counts_shared_values() {
+    let mut engine = MeteorEngine::new();
+    engine.set("app:ui:theme", "dark").unwrap();
+    engine.set("app:ui:border", "dark").unwrap();
+    engine.set("user:prefs:theme", "dark").unwrap();
+    engine.set("app:main:enabled", "true").unwrap();
+    engine.set("user:prefs:sync", "true").unwrap();
+    engine.set("app:main:name", "meteor").unwrap();
+
+    let histogram = engine.value_histogram();
+    assert_eq!(histogram.len(), 3);
+    assert_eq!(histogram["dark"], 3);
+    assert_eq!(histogram["true"], 2);
+    assert_eq!(histogram["meteor"], 1);
+}
+
+#[test]
+fn test_tokens_iter_yields_one_meteor_per_key() {
+    let mut engine = MeteorEngine::new();
+    engine.set("app:ui:button", "click").unwrap();
+    engine.set("app:ui:list[0]", "first").unwrap();
+    engine.set("app:main:debug", "true").unwrap();
+    engine.set("user:settings.ui:theme", "dark").unwrap();
+
+    let meteors: Vec<_> = engine.tokens_iter().collect();
+    assert_eq!(meteors.len(), engine.iter_entries().count());
+    assert_eq!(meteors.len(), 4);
+
+    let flat: Vec<_> = meteors
+        .iter()
+        .map(|meteor| {
+            assert_eq!(meteor.tokens().len(), 1);
+            let token = &meteor.tokens()[0];
+            (
+                meteor.context().name().to_string(),
+                meteor.namespace().to_string(),
+                token.key_notation().to_string(),
+                token.value().to_string(),
+            )
+        })
+        .collect();
+    let entries: Vec<_> = engine.iter_entries().collect();
+    assert_eq!(flat, entries);
+}
@@ -0,0 +1,703 @@
+//! Test MeteorEngine token-level namespace merging
+
+use meteor::types::{
+    Context, ExportFormat, ImportDiff, MergeStrategy, Meteor, MeteorEngine, MeteorShower,
+    Namespace, Token,
+};
+
+fn engines_with_conflict() -> (MeteorEngine, MeteorEngine) {
+    let mut base = MeteorEngine::new();
+    base.set("app:ui:theme", "dark").unwrap();
+    base.set("app:ui:lang", "en").unwrap();
+
+    let mut other = MeteorEngine::new();
+    other.set("user:ui:theme", "light").unwrap();
+    other.set("user:ui:lang", "en").unwrap();
+    other.set("user:ui:button", "click").unwrap();
+
+    (base, other)
+}
+
+#[test]
+fn test_merge_keep_existing_preserves_conflicting_values() {
+    let (mut base, other) = engines_with_conflict();
+
+    let written = base
+        .merge_namespace_from(
+            &other,
+            "user",
+            "ui",
+            "app",
+            "ui",
+            MergeStrategy::KeepExisting,
+        )
+        .unwrap();
+
+    assert_eq!(written, 1);
+    assert_eq!(base.get("app:ui:theme"), Some("dark"));
+    assert_eq!(base.get("app:ui:lang"), Some("en"));
+    assert_eq!(base.get("app:ui:button"), Some("click"));
+}
+
+#[test]
+fn test_merge_overwrite_replaces_conflicting_values() {
+    let (mut base, other) = engines_with_conflict();
+
+    let written = base
+        .merge_namespace_from(&other, "user", "ui", "app", "ui", MergeStrategy::Overwrite)
+        .unwrap();
+
+    // theme overwritten, button added, identical lang skipped
+    assert_eq!(written, 2);
+    assert_eq!(base.get("app:ui:theme"), Some("light"));
+    assert_eq!(base.get("app:ui:lang"), Some("en"));
+    assert_eq!(base.get("app:ui:button"), Some("click"));
+}
+
+#[test]
+fn test_merge_fail_on_conflict_leaves_destination_untouched() {
+    let (mut base, other) = engines_with_conflict();
+
+    let err = base
+        .merge_namespace_from(
+            &other,
+            "user",
+            "ui",
+            "app",
+            "ui",
+            MergeStrategy::FailOnConflict,
+        )
+        .unwrap_err();
+
+    assert!(err.contains("theme"));
+    assert!(!err.contains("lang"));
+    assert_eq!(base.get("app:ui:theme"), Some("dark"));
+    assert_eq!(base.get("app:ui:button"), None);
+}
+
+#[test]
+fn test_merge_fail_on_conflict_succeeds_without_conflicts() {
+    let mut base = MeteorEngine::new();
+    base.set("app:ui:theme", "dark").unwrap();
+
+    let mut other = MeteorEngine::new();
+    other.set("app:ui:theme", "dark").unwrap();
+    other.set("app:ui:button", "click").unwrap();
+
+    let written = base
+        .merge_namespace_from(
+            &other,
+            "app",
+            "ui",
+            "app",
+            "ui",
+            MergeStrategy::FailOnConflict,
+        )
+        .unwrap();
+
+    assert_eq!(written, 1);
+    assert_eq!(base.get("app:ui:button"), Some("click"));
+}
+
+#[test]
+fn test_merge_appends_new_keys_in_source_order() {
+    let mut base = MeteorEngine::new();
+    base.set("app:ui:first", "1").unwrap();
+
+    let mut other = MeteorEngine::new();
+    other.set("app:ui:zebra", "z").unwrap();
+    other.set("app:ui:apple", "a").unwrap();
+
+    base.merge_namespace_from(&other, "app", "ui", "app", "ui", MergeStrategy::Overwrite)
+        .unwrap();
+
+    let view = base.namespace_view("app", "ui").unwrap();
+    let keys: Vec<&str> = view.keys().collect();
+    assert_eq!(keys, vec!["first", "zebra", "apple"]);
+}
+
+#[test]
+fn test_merge_missing_source_namespace_is_noop() {
+    let (mut base, other) = engines_with_conflict();
+
+    let written = base
+        .merge_namespace_from(
+            &other,
+            "user",
+            "missing",
+            "app",
+            "ui",
+            MergeStrategy::Overwrite,
+        )
+        .unwrap();
+
+    assert_eq!(written, 0);
+    assert_eq!(base.get("app:ui:theme"), Some("dark"));
+}
+
+#[test]
+fn test_import_prefixed_twice_lands_separately() {
+    let mut source = MeteorEngine::new();
+    source.set("app:ui:theme", "dark").unwrap();
+    source.set("app:ui:lang", "en").unwrap();
+    let export = source
+        .export_namespace("app", "ui", ExportFormat::Text)
+        .unwrap();
+
+    let mut engine = MeteorEngine::new();
+    let first = engine
+        .import_namespace_prefixed(export.clone(), "imports", "a")
+        .unwrap();
+    let second = engine
+        .import_namespace_prefixed(export, "imports", "b")
+        .unwrap();
+
+    assert_eq!(first.tokens_added, 2);
+    assert_eq!(second.tokens_added, 2);
+    assert!(first.checksum_valid && second.checksum_valid);
+    assert_eq!(engine.get("imports:a.ui:theme"), Some("dark"));
+    assert_eq!(engine.get("imports:b.ui:theme"), Some("dark"));
+    assert_eq!(engine.get("app:ui:theme"), None);
+    assert_eq!(
+        engine.namespaces_in_context("imports"),
+        vec!["a.ui".to_string(), "b.ui".to_string()]
+    );
+
+    assert_eq!(
+        second.diff[1],
+        ImportDiff::Added {
+            key: "imports:b.ui:lang".to_string(),
+            value: "en".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_namespace_diff_categorizes_keys() {
+    let mut engine = MeteorEngine::new();
+    engine.set("config:prod:host", "prod.example").unwrap();
+    engine.set("config:prod:port", "443").unwrap();
+    engine.set("config:prod:replicas", "5").unwrap();
+    engine
+        .set("config:staging:host", "staging.example")
+        .unwrap();
+    engine.set("config:staging:port", "443").unwrap();
+    engine.set("config:staging:debug", "true").unwrap();
+
+    let diff = engine.namespace_diff(("config", "prod"), ("config", "staging"));
+
+    assert_eq!(
+        diff,
+        vec![
+            ImportDiff::Updated {
+                key: "host".to_string(),
+                old_value: "prod.example".to_string(),
+                new_value: "staging.example".to_string(),
+            },
+            ImportDiff::Unchanged {
+                key: "port".to_string(),
+            },
+            ImportDiff::Removed {
+                key: "replicas".to_string(),
+                value: "5".to_string(),
+            },
+            ImportDiff::Added {
+                key: "debug".to_string(),
+                value: "true".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_namespace_diff_against_missing_namespace() {
+    let mut engine = MeteorEngine::new();
+    engine.set("app:ui:theme", "dark").unwrap();
+
+    let diff = engine.namespace_diff(("app", "ui"), ("app", "missing"));
+    assert_eq!(
+        diff,
+        vec![ImportDiff::Removed {
+            key: "theme".to_string(),
+            value: "dark".to_string(),
+        }]
+    );
+    assert!(engine
+        .namespace_diff(("app", "missing"), ("app", "gone"))
+        .is_empty());
+}
+
+#[test]
+fn test_apply_meteors_stores_parsed_vector() {
+    let shower =
+        MeteorShower::parse("app:ui:button=click;theme=dark :;: user:settings.display:list[0]=a")
+            .unwrap();
+    let mut meteors = shower.meteors().to_vec();
+    meteors.extend(Meteor::parse("doc:guide:intro=Welcome").unwrap());
+
+    let mut engine = MeteorEngine::new();
+    assert_eq!(engine.apply_meteors(&meteors), Ok(4));
+
+    assert_eq!(engine.get("app:ui:button"), Some("click"));
+    assert_eq!(engine.get("app:ui:theme"), Some("dark"));
+    assert_eq!(engine.get("user:settings.display:list[0]"), Some("a"));
+    assert_eq!(engine.get("doc:guide:intro"), Some("Welcome"));
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_apply_meteors_is_all_or_nothing() {
+    let good = Meteor::parse("app:ui:button=click").unwrap().remove(0);
+    let bad = Meteor::force_create_unchecked(
+        Context::app(),
+        Namespace::from_string("ui"),
+        vec![Token::new_with_namespace(
+            Namespace::from_string("other"),
+            "theme",
+            "dark",
+        )],
+    );
+
+    let mut engine = MeteorEngine::new();
+    assert!(engine.apply_meteors(&[good, bad]).is_err());
+    assert!(engine.is_empty());
+}
+
+fn three_exports() -> Vec<meteor::types::ExportData> {
+    let mut source = MeteorEngine::new();
+    source.set("app:ui:theme", "dark").unwrap();
+    source.set("app:ui:lang", "en").unwrap();
+    source.set("user:prefs:theme", "light").unwrap();
+    source.set("user:prefs:font", "mono").unwrap();
+    source.set("system:defaults:lang", "en").unwrap();
+    source.set("system:defaults:timeout", "30").unwrap();
+
+    [("app", "ui"), ("user", "prefs"), ("system", "defaults")]
+        .iter()
+        .map(|(ctx, ns)| {
+            source
+                .export_namespace(ctx, ns, ExportFormat::Text)
+                .unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn test_import_all_into_unions_keys() {
+    let exports = three_exports();
+
+    let mut keep = MeteorEngine::new();
+    keep.set("merged:all:timeout", "10").unwrap();
+    let written = keep
+        .import_all_into(&exports, ("merged", "all"), MergeStrategy::KeepExisting)
+        .unwrap();
+    assert_eq!(written, 3);
+    assert_eq!(keep.get("merged:all:theme"), Some("dark"));
+    assert_eq!(keep.get("merged:all:lang"), Some("en"));
+    assert_eq!(keep.get("merged:all:font"), Some("mono"));
+    assert_eq!(keep.get("merged:all:timeout"), Some("10"));
+
+    let mut overwrite = MeteorEngine::new();
+    overwrite.set("merged:all:timeout", "10").unwrap();
+    let written = overwrite
+        .import_all_into(&exports, ("merged", "all"), MergeStrategy::Overwrite)
+        .unwrap();
+    assert_eq!(written, 4);
+    assert_eq!(overwrite.get("merged:all:theme"), Some("light"));
+    assert_eq!(overwrite.get("merged:all:timeout"), Some("30"));
+    let view = overwrite.namespace_view("merged", "all").unwrap();
+    let keys: Vec<&str> = view.keys().collect();
+    assert_eq!(keys, vec!["timeout", "theme", "lang", "font"]);
+}
+
+#[test]
+fn test_import_all_into_fail_on_conflict_writes_nothing() {
+    let exports = three_exports();
+
+    let mut engine = MeteorEngine::new();
+    let err = engine
+        .import_all_into(&exports, ("merged", "all"), MergeStrategy::FailOnConflict)
+        .unwrap_err();
+    assert!(err.contains("theme"));
+    assert!(engine.is_empty());
+
+    // The identical `lang` values are not a conflict
+    let written = engine
+        .import_all_into(
+            &[exports[0].clone(), exports[2].clone()],
+            ("merged", "all"),
+            MergeStrategy::FailOnConflict,
+        )
+        .unwrap();
+    assert_eq!(written, 3);
+}
+
+fn migration_engine() -> MeteorEngine {
+    let mut engine = MeteorEngine::new();
+    engine.set("app:db:host", "localhost").unwrap();
+    engine.set("app:db:port", "5432").unwrap();
+    engine.set("app:cache:ttl", "60").unwrap();
+    engine
+}
+
+#[test]
+fn test_bulk_rename_applies_mapping() {
+    let mut engine = migration_engine();
+
+    let moved = engine
+        .bulk_rename(&[
+            ("app:db:host", "app:database:host"),
+            ("app:db:port", "app:database:port"),
+            ("app:cache:ttl", "system:cache:ttl_seconds"),
+        ])
+        .unwrap();
+
+    assert_eq!(moved, 3);
+    assert_eq!(engine.get("app:database:host"), Some("localhost"));
+    assert_eq!(engine.get("app:database:port"), Some("5432"));
+    assert_eq!(engine.get("system:cache:ttl_seconds"), Some("60"));
+    assert_eq!(engine.get("app:db:host"), None);
+    assert_eq!(engine.get("app:cache:ttl"), None);
+
+    // Swapping two keys is not a collision
+    engine
+        .bulk_rename(&[
+            ("app:database:host", "app:database:port"),
+            ("app:database:port", "app:database:host"),
+        ])
+        .unwrap();
+    assert_eq!(engine.get("app:database:host"), Some("5432"));
+    assert_eq!(engine.get("app:database:port"), Some("localhost"));
+}
+
+#[test]
+fn test_bulk_rename_missing_source_changes_nothing() {
+    let mut engine = migration_engine();
+
+    let err = engine
+        .bulk_rename(&[
+            ("app:db:host", "app:database:host"),
+            ("app:db:user", "app:database:user"),
+        ])
+        .unwrap_err();
+
+    assert!(err.contains("app:db:user"));
+    assert_eq!(engine.get("app:db:host"), Some("localhost"));
+    assert_eq!(engine.get("app:database:host"), None);
+}
+
+#[test]
+fn test_bulk_rename_collision_changes_nothing() {
+    let mut engine = migration_engine();
+
+    // Two sources onto one target
+    assert!(engine
+        .bulk_rename(&[
+            ("app:db:host", "app:database:endpoint"),
+            ("app:db:port", "app:database:endpoint"),
+        ])
+        .is_err());
+
+    // Target already holds a value that is not being moved
+    let err = engine
+        .bulk_rename(&[
+            ("app:db:port", "app:database:port"),
+            ("app:db:host", "app:cache:ttl"),
+        ])
+        .unwrap_err();
+    assert!(err.contains("app:cache:ttl"));
+
+    assert_eq!(engine.get("app:db:host"), Some("localhost"));
+    assert_eq!(engine.get("app:db:port"), Some("5432"));
+    assert_eq!(engine.get("app:cache:ttl"), Some("60"));
+    assert!(!engine.namespace_exists_fast("app", "database"));
+}
+
+#[test]
+fn test_bulk_rename_bracket_collision_changes_nothing() {
+    let mut engine = migration_engine();
+    engine.set("app:cache:list__i_0", "cached").unwrap();
+
+    // Target transforms onto a key that stays put
+    let err = engine
+        .bulk_rename(&[("app:db:host", "app:cache:list[0]")])
+        .unwrap_err();
+    assert!(err.contains("list__i_0"));
+
+    // Two targets that transform to the same key
+    assert!(engine
+        .bulk_rename(&[
+            ("app:db:host", "app:database:list[0]"),
+            ("app:db:port", "app:database:list__i_0"),
+        ])
+        .is_err());
+
+    assert_eq!(engine.get("app:db:host"), Some("localhost"));
+    assert_eq!(engine.get("app:db:port"), Some("5432"));
+    assert_eq!(engine.get("app:cache:list__i_0"), Some("cached"));
+    assert!(!engine.namespace_exists_fast("app", "database"));
+
+    // Moving the existing key away frees its transformed form
+    let moved = engine
+        .bulk_rename(&[
+            ("app:cache:list__i_0", "app:cache:old"),
+            ("app:db:host", "app:cache:list[0]"),
+        ])
+        .unwrap();
+    assert_eq!(moved, 2);
+    assert_eq!(engine.get("app:cache:list[0]"), Some("localhost"));
+    assert_eq!(engine.get("app:cache:old"), Some("cached"));
+}
+
+#[test]
+fn test_bulk_rename_applies_target_validator() {
+    let mut engine = migration_engine();
+    engine.set_value_validator(|context, _, _, value| {
+        if context == "system" && value.parse::<u32>().is_err() {
+            Err(format!("system values must be numeric: {}", value))
+        } else {
+            Ok(())
+        }
+    });
+
+    let err = engine
+        .bulk_rename(&[
+            ("app:db:port", "system:db:port"),
+            ("app:db:host", "system:db:host"),
+        ])
+        .unwrap_err();
+    assert!(err.contains("localhost"));
+    assert_eq!(engine.get("app:db:port"), Some("5432"));
+    assert_eq!(engine.get("app:db:host"), Some("localhost"));
+    assert!(!engine.namespace_exists_fast("system", "db"));
+}
+
+#[test]
+fn test_changes_since_checkpoint_reports_exact_changes() {
+    let mut engine = MeteorEngine::new();
+    engine.set("app:ui:theme", "dark").unwrap();
+    engine.set("app:ui:lang", "en").unwrap();
+    engine.set("user:prefs:font", "mono").unwrap();
+    engine.checkpoint();
+    assert!(engine.changes_since_checkpoint().is_empty());
+
+    engine.set("app:ui:theme", "light").unwrap();
+    engine.delete("user:prefs:font").unwrap();
+    engine.set("app:ui:button", "click").unwrap();
+    engine.set("app:ui:lang", "en").unwrap();
+
+    assert_eq!(
+        engine.changes_since_checkpoint(),
+        vec![
+            ImportDiff::Updated {
+                key: "app:ui:theme".to_string(),
+                old_value: "dark".to_string(),
+                new_value: "light".to_string(),
+            },
+            ImportDiff::Removed {
+                key: "user:prefs:font".to_string(),
+                value: "mono".to_string(),
+            },
+            ImportDiff::Added {
+                key: "app:ui:button".to_string(),
+                value: "click".to_string(),
+            },
+        ]
+    );
+
+    // A new checkpoint becomes the baseline
+    engine.checkpoint();
+    assert!(engine.changes_since_checkpoint().is_empty());
+    engine.delete("app:ui:button").unwrap();
+    assert_eq!(
+        engine.changes_since_checkpoint(),
+        vec![ImportDiff::Removed {
+            key: "app:ui:button".to_string(),
+            value: "click".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_shower_export_round_trips_through_engine() {
+    let shower = MeteorShower::parse(
+        "app:ui:button=click;theme=dark :;: user:settings.display:list[0]=a;list[1]=b :;: app:ui:theme=light",
+    )
+    .unwrap();
+
+    let exports = shower.export(ExportFormat::Json);
+    assert_eq!(exports.len(), 2);
+
+    let mut engine = MeteorEngine::new();
+    for export in exports {
+        let json = export.to_json().unwrap();
+        let parsed = meteor::types::ExportData::from_json(&json).unwrap();
+        assert!(parsed.verify_checksum(&meteor::types::DefaultChecksum));
+        let result = engine.import_namespace(parsed).unwrap();
+        assert!(result.success && result.checksum_valid);
+    }
+
+    assert_eq!(engine.get("app:ui:button"), Some("click"));
+    assert_eq!(engine.get("app:ui:theme"), Some("light"));
+    assert_eq!(engine.get("user:settings.display:list[0]"), Some("a"));
+    assert_eq!(engine.get("user:settings.display:list[1]"), Some("b"));
+    assert_eq!(engine.iter_entries().count(), 4);
+}
+
+#[test]
+fn test_merge_with_concatenating_reducer() {
+    let mut base = MeteorEngine::new();
+    base.set("app:ui:tags", "a,b").unwrap();
+    base.set("app:ui:theme", "dark").unwrap();
+    base.set("app:ui:lang", "en").unwrap();
+
+    let mut other = MeteorEngine::new();
+    other.set("app:ui:tags", "c").unwrap();
+    other.set("app:ui:lang", "fr").unwrap();
+    other.set("app:ui:button", "click").unwrap();
+    other.set("user:prefs:font", "mono").unwrap();
+
+    let written = base
+        .merge_with(&other, |existing, incoming| {
+            format!("{},{}", existing, incoming)
+        })
+        .unwrap();
+
+    assert_eq!(written, 4);
+    assert_eq!(base.get("app:ui:tags"), Some("a,b,c"));
+    assert_eq!(base.get("app:ui:lang"), Some("en,fr"));
+    assert_eq!(base.get("app:ui:theme"), Some("dark"));
+    assert_eq!(base.get("app:ui:button"), Some("click"));
+    assert_eq!(base.get("user:prefs:font"), Some("mono"));
+    assert_eq!(other.get("app:ui:tags"), Some("c"));
+}
+
+#[test]
+fn test_sync_to_converges_on_target() {
+    let mut engine = MeteorEngine::new();
+    engine.set("app:ui:theme", "dark").unwrap();
+    engine.set("app:ui:lang", "en").unwrap();
+    engine.set("app:legacy:flag", "1").unwrap();
+    engine.set("user::name", "ada").unwrap();
+
+    let mut target = MeteorEngine::new();
+    target.set("app:ui:theme", "light").unwrap();
+    target.set("app:ui:lang", "en").unwrap();
+    target.set("app:ui:list[0]", "a").unwrap();
+    target.set("system:config:port", "8080").unwrap();
+    target.set("user::name", "ada").unwrap();
+
+    let applied = engine.sync_to(&target);
+    assert_eq!(
+        applied,
+        vec![
+            ImportDiff::Removed {
+                key: "app:legacy:flag".to_string(),
+                value: "1".to_string(),
+            },
+            ImportDiff::Updated {
+                key: "app:ui:theme".to_string(),
+                old_value: "dark".to_string(),
+                new_value: "light".to_string(),
+            },
+            ImportDiff::Added {
+                key: "app:ui:list[0]".to_string(),
+                value: "a".to_string(),
+            },
+            ImportDiff::Added {
+                key: "system:config:port".to_string(),
+                value: "8080".to_string(),
+            },
+        ]
+    );
+
+    let mut synced: Vec<_> = engine.iter_entries().collect();
+    let mut expected: Vec<_> = target.iter_entries().collect();
+    synced.sort();
+    expected.sort();
+    assert_eq!(synced, expected);
+    assert!(!engine.namespace_exists_fast("app", "legacy"));
+
+    assert!(engine.sync_to(&target).is_empty());
+}
+
+#[test]
+fn test_import_failure_leaves_namespace_unchanged() {
+    let mut source = MeteorEngine::new();
+    source.set("app:ui:theme", "light").unwrap();
+    source.set("app:ui:bad=key", "x").unwrap();
+    source.set("app:ui:lang", "en").unwrap();
+    let export = source
+        .export_namespace("app", "ui", ExportFormat::Text)
+        .unwrap();
+
+    let mut target = MeteorEngine::new();
+    target.set_strict_chars(true);
+    target.set("app:ui:theme", "dark").unwrap();
+
+    assert!(target.import_namespace(export).is_err());
+    assert_eq!(target.get("app:ui:theme"), Some("dark"));
+    assert_eq!(target.get("app:ui:lang"), None);
+    assert_eq!(
+        target.namespace_view("app", "ui").unwrap().keys().count(),
+        1
+    );
+}
+
+#[test]
+fn test_preview_stream_matches_real_processing() {
+    let mut engine = MeteorEngine::new();
+    engine.set("app:ui:theme", "dark").unwrap();
+    engine.set("app:ui:lang", "en").unwrap();
+    engine.set("user:prefs:font", "mono").unwrap();
+
+    let stream = "app:ui:theme=light :;: ctl:delete=user:prefs:font :;: app:ui:lang=en :;: app:db:host=localhost";
+    let preview = engine.preview_stream(stream).unwrap();
+
+    // Previewing leaves the engine untouched
+    assert_eq!(engine.get("app:ui:theme"), Some("dark"));
+    assert_eq!(engine.get("user:prefs:font"), Some("mono"));
+    assert!(engine.command_history().is_empty());
+
+    engine.checkpoint();
+    meteor::MeteorStreamParser::process(&mut engine, stream).unwrap();
+    assert_eq!(preview, engine.changes_since_checkpoint());
+    assert_eq!(preview.len(), 3);
+
+    assert!(engine.preview_stream("app:ui:flag").is_err());
+}
+
+#[test]
+fn test_merge_with_applies_write_checks() {
+    let mut base = MeteorEngine::new();
+    base.set("app:ui:tags", "a").unwrap();
+    base.set_value_validator(|_, _, key, value| {
+        if key == "tags" && value.len() > 3 {
+            Err(format!("tags too long: {}", value))
+        } else {
+            Ok(())
+        }
+    });
+
+    let mut other = MeteorEngine::new();
+    other.set("app:ui:button", "click").unwrap();
+    other.set("app:ui:tags", "b,c").unwrap();
+
+    let result = base.merge_with(&other, |existing, incoming| {
+        format!("{},{}", existing, incoming)
+    });
+    assert!(result.unwrap_err().contains("tags too long"));
+    assert_eq!(base.get("app:ui:tags"), Some("a"));
+    assert_eq!(base.get("app:ui:button"), None);
+    let last = base.last_command().unwrap();
+    assert_eq!(last.command_type, "merge_with");
+    assert!(!last.success);
+
+    // Reduced values are normalized like any other write
+    base.clear_value_validator();
+    base.set_value_normalization(meteor::types::NormalizeMode::TrimEnds);
+    let written = base.merge_with(&other, |_, incoming| format!(" {} ", incoming));
+    assert_eq!(written, Ok(2));
+    assert_eq!(base.get("app:ui:tags"), Some("b,c"));
+    assert!(base.last_command().unwrap().success);
+}
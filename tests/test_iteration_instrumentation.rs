@@ -171,3 +171,56 @@ fn test_iteration_instrumentation_cumulative() {
     );
     assert_eq!(status_after_second.avg_keys_per_iteration, 2.0);
 }
+
+#[cfg(feature = "workspace-instrumentation")]
+#[test]
+fn test_metrics_sink_receives_events() {
+    use meteor::types::{MeteorEngine, MetricEvent};
+    use std::sync::{Arc, Mutex};
+
+    let mut engine = MeteorEngine::new();
+    engine.set("app:ui:button", "click").unwrap();
+    engine.set("app:ui:theme", "dark").unwrap();
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink_events = Arc::clone(&events);
+    engine.set_metrics_sink(move |event| sink_events.lock().unwrap().push(event));
+
+    let view = engine.namespace_view("app", "ui").unwrap();
+    assert_eq!(view.find_keys("b*"), vec!["button".to_string()]);
+    assert_eq!(view.find_keys("b*"), vec!["button".to_string()]);
+    let _entries: Vec<_> = engine.iter_entries().collect();
+
+    let pattern_event = |hit: bool| {
+        let (context, namespace, pattern) = ("app".to_string(), "ui".to_string(), "b*".to_string());
+        if hit {
+            MetricEvent::CacheHit {
+                context,
+                namespace,
+                pattern,
+            }
+        } else {
+            MetricEvent::CacheMiss {
+                context,
+                namespace,
+                pattern,
+            }
+        }
+    };
+    assert_eq!(
+        *events.lock().unwrap(),
+        vec![
+            pattern_event(false),
+            pattern_event(true),
+            MetricEvent::Iteration {
+                context: "app".to_string(),
+                namespace: "ui".to_string(),
+                keys: 2,
+            },
+        ]
+    );
+
+    assert!(engine.clear_metrics_sink());
+    let _entries: Vec<_> = engine.iter_entries().collect();
+    assert_eq!(events.lock().unwrap().len(), 3);
+}